@@ -9,7 +9,7 @@ use clap::Parser;
 use std::path::PathBuf;
 
 /// Command-line arguments structure.
-#[derive(Parser, Debug)]
+#[derive(Parser, Debug, Clone)]
 #[command(version, about, long_about = None)]
 pub struct Args {
     #[command(subcommand)]
@@ -73,17 +73,29 @@ pub struct Args {
     pub context_name: String,
 
     /// Path to a custom data directory.
-    #[arg(short = 'd', long)]
+    #[arg(short = 'd', long, global = true)]
     pub data_dir: Option<PathBuf>,
 
     /// Show extended supply levels (drums, fusers, etc).
-    #[arg(short = 'e', long)]
+    #[arg(short = 'e', long, global = true)]
     pub extra_supplies: bool,
 
     /// Show printer usage metrics (page counts).
-    #[arg(short = 'm', long)]
+    #[arg(short = 'm', long, global = true)]
     pub metrics: bool,
 
+    /// Show device identity (firmware, MAC address, uptime, location, contact).
+    #[arg(short = 'i', long, global = true)]
+    pub info: bool,
+
+    /// Show paper tray levels and output bins.
+    #[arg(long, global = true)]
+    pub paper: bool,
+
+    /// Explain which data file entry was used and where each OID mapping came from.
+    #[arg(long, global = true)]
+    pub explain: bool,
+
     /// Timeout in seconds.
     #[arg(short = 't', long, default_value_t = 5)]
     pub timeout: u64,
@@ -96,7 +108,23 @@ pub struct Args {
     #[arg(long, default_value_t = CliTheme::default())]
     pub theme: CliTheme,
 
+    /// Record this reading in the local history store.
+    #[arg(long, global = true)]
+    pub record: bool,
+
+    /// Re-poll the printer every N seconds and redraw the output.
+    #[arg(short = 'w', long, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+    pub watch: Option<u64>,
+
     /// Select the output format.
-    #[arg(short = 'o', long, default_value_t = OutputFormat::default())]
+    #[arg(short = 'o', long, default_value_t = OutputFormat::default(), global = true)]
     pub output: OutputFormat,
+
+    /// Supply level (%) at or below which the check reports WARNING.
+    #[arg(long, default_value_t = 20, global = true, help_heading = "Nagios")]
+    pub warning: i64,
+
+    /// Supply level (%) at or below which the check reports CRITICAL.
+    #[arg(long, default_value_t = 10, global = true, help_heading = "Nagios")]
+    pub critical: i64,
 }
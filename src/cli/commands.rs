@@ -1,4 +1,5 @@
 use clap::Subcommand;
+use std::{net::SocketAddr, path::PathBuf};
 
 #[derive(Subcommand, Debug, Clone)]
 pub enum Commands {
@@ -6,4 +7,47 @@ pub enum Commands {
         #[arg(short = 't', long, default_value_t = 5)]
         timeout: u64,
     },
+    /// Query every printer in the inventory (or the given aliases) at once.
+    Fleet {
+        /// Aliases to query. Queries the whole inventory when omitted.
+        aliases: Vec<String>,
+
+        /// Maximum number of printers queried concurrently.
+        #[arg(short = 'j', long, default_value_t = 8)]
+        jobs: usize,
+    },
+    /// Run a Prometheus exporter serving `/metrics` and `/probe?target=<alias|host>`.
+    Serve {
+        /// Address and port to listen on.
+        #[arg(short = 'L', long, default_value = "0.0.0.0:9781")]
+        listen: SocketAddr,
+
+        /// Maximum number of printers queried concurrently on `/metrics`.
+        #[arg(short = 'j', long, default_value_t = 8)]
+        jobs: usize,
+    },
+    /// Show recorded readings and supply trends for a printer.
+    History {
+        /// Inventory alias (or host) the readings were recorded under.
+        alias: String,
+
+        /// Only show the most recent N readings.
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+    },
+    /// Work with printer data files.
+    Data {
+        #[command(subcommand)]
+        command: DataCommands,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum DataCommands {
+    /// Check data files for syntax errors, unknown keys, malformed OIDs and duplicate models.
+    Validate {
+        /// Directory to check. Checks `--data-dir`, the user data directory and the
+        /// bundled data when omitted.
+        dir: Option<PathBuf>,
+    },
 }
@@ -1,6 +1,17 @@
 use crate::{
     cli::{output::OutputFormat, progress::show_progress, theme::CliTheme},
-    printer::Printer,
+    fleet::FleetRecord,
+    history::{HistoryEntry, SupplyTrend},
+    printer::{
+        Printer,
+        alert::{AlertSeverity, PrinterAlert},
+        load::DataSource,
+        output::OutputBin,
+        status::{Availability, DeviceStatus},
+        supply::{PrinterSupply, Supply, SupplyState, toner::Toner},
+        tray::Tray,
+        validate::Problem,
+    },
 };
 use colored::Colorize;
 
@@ -9,6 +20,8 @@ pub fn show_printer_values(
     printer: Printer,
     extra_supplies: bool,
     metrics: bool,
+    paper: bool,
+    info: bool,
     theme: &CliTheme,
     output: &OutputFormat,
 ) {
@@ -25,62 +38,134 @@ pub fn show_printer_values(
         return;
     }
 
-    // Since Toner, Drum, and Fuser are different structs, we use a macro
-    // to access the `.level_percent` field regardless of the specific type.
-    macro_rules! render {
-        ($label:literal, $label_color:literal, $target:expr, $bar_color:expr) => {
-            // Check if the parent struct exists
-            if let Some(item) = $target.as_ref() {
-                // Check if the `level_percent` field exists within it
-                if let Some(level) = item.level_percent {
-                    show_progress($label, $label_color, level as u8, $bar_color, *theme);
-                }
-            }
-        };
-    }
+    // Renders one bar, with the forecast of the supply next to it
+    let render = |label: &str, supply: &Supply, colors: (&str, &str)| {
+        let note = printer
+            .forecast(&supply.label())
+            .map(|f| f.summary())
+            .unwrap_or_default();
+
+        show_progress(label, colors.0, supply.state, colors.1, *theme, &note);
+    };
 
     // Header
-    println!("{} {}", "Printer:".bright_cyan().bold(), printer.name);
+    match printer.status_summary() {
+        Some(status) => println!(
+            "{} {} {}",
+            "Printer:".bright_cyan().bold(),
+            printer.name,
+            status_label(&printer, format!("({status})"))
+        ),
+        None => println!("{} {}", "Printer:".bright_cyan().bold(), printer.name),
+    }
+
+    if let Some(message) = &printer.console_message {
+        println!("{} {message}", "Console:".bright_cyan().bold());
+    }
 
     if extra_supplies && let Some(serial) = &printer.serial_number {
         println!("{} {serial}", "Serial:".bright_cyan().bold());
     }
     println!();
 
-    // Toners
-    println!("--> {}\n", "Toner:".bright_white().bold());
-    let t = &printer.toners;
+    // Identity
+    if info && let Some(identity) = &printer.identity {
+        println!("--> {}\n", "Identity:".bright_white().bold());
+
+        for (label, value) in identity.fields() {
+            println!("{} {value}", format!("{label}:").bright_cyan().bold());
+        }
+
+        if let Some(source) = &printer.data_source {
+            println!("{} {source}", "Data file:".bright_cyan().bold());
+        }
+
+        println!("\n");
+    }
+
+    // Alerts
+    if !printer.alerts.is_empty() {
+        println!("--> {}\n", "Alerts:".bright_white().bold());
+
+        for alert in &printer.alerts {
+            println!("{}", alert_line(alert));
+        }
 
-    render!("Black", "bright_white", t.black_toner, "white");
-    render!("Cyan", "bright_cyan", t.cyan_toner, "cyan");
-    render!("Magenta", "bright_magenta", t.magenta_toner, "magenta");
-    render!("Yellow", "bright_yellow", t.yellow_toner, "yellow");
+        println!("\n");
+    }
+
+    // Toners, or inks on an inkjet printer
+    let toners: Vec<&Supply> = printer.supplies_of(PrinterSupply::Toner).collect();
+    let heading = if !toners.is_empty() && toners.iter().all(|t| t.is_ink()) {
+        "Ink:"
+    } else {
+        "Toner:"
+    };
+    println!("--> {}\n", heading.bright_white().bold());
+
+    for toner in toners {
+        render(&color_label(toner), toner, bar_colors(toner));
+    }
 
     // Extra Supplies
     if extra_supplies {
-        let d = &printer.drums;
-
         println!("\n\n--> {}\n", "Drum:".bright_white().bold());
 
-        render!("Black", "bright_white", d.black_drum, "white");
-        render!("Cyan", "bright_cyan", d.cyan_drum, "cyan");
-        render!("Magenta", "bright_magenta", d.magenta_drum, "magenta");
-        render!("Yellow", "bright_yellow", d.yellow_drum, "yellow");
+        for drum in printer.supplies_of(PrinterSupply::Drum) {
+            render(&color_label(drum), drum, bar_colors(drum));
+        }
 
         // Other parts
-        if printer.fuser.is_some() || printer.reservoir.is_some() {
+        let fusers = printer.supplies_of(PrinterSupply::Fuser);
+        let reservoirs = printer.supplies_of(PrinterSupply::Reservoir);
+        let rest = printer.supplies_of(PrinterSupply::Other);
+        let others: Vec<&Supply> = fusers.chain(reservoirs).chain(rest).collect();
+
+        if !others.is_empty() {
             println!("\n\n--> {}\n", "Other:".bright_white().bold());
 
-            render!("Fuser", "white", printer.fuser, "white");
+            for supply in others {
+                render(&supply.label(), supply, ("white", class_bar_color(supply)));
+            }
+        }
+    }
+
+    // Paper trays
+    if paper && !printer.trays.is_empty() {
+        println!("\n\n--> {}\n", "Paper:".bright_white().bold());
 
-            let reservoir_color = printer
-                .reservoir
-                .as_ref()
-                .and_then(|r| r.level_percent)
-                .map(|l| if l == 100 { "green" } else { "red" })
-                .unwrap_or("white");
+        for tray in &printer.trays {
+            let empty =
+                tray.state == SupplyState::Exact(0) || tray.status.critical || tray.status.offline;
+            let label_color = if empty { "bright_red" } else { "bright_white" };
 
-            render!("Reservoir", "white", printer.reservoir, reservoir_color);
+            show_progress(
+                &tray.name,
+                label_color,
+                tray.state,
+                "white",
+                *theme,
+                &tray_note(tray),
+            );
+        }
+    }
+
+    // Output bins
+    if paper && !printer.outputs.is_empty() {
+        println!("\n\n--> {}\n", "Output:".bright_white().bold());
+
+        for bin in &printer.outputs {
+            let full = bin.is_full() || bin.status.is_some_and(|s| s.critical || s.offline);
+            let label_color = if full { "bright_red" } else { "bright_white" };
+
+            show_progress(
+                &bin.name,
+                label_color,
+                bin.state,
+                "white",
+                *theme,
+                &output_note(bin),
+            );
         }
     }
 
@@ -105,44 +190,319 @@ pub fn show_printer_values(
     println!();
 }
 
-/// Formats and prints the printer data as a CSV record to standard output.
+/// Explains where the OID mappings of a printer came from: the data sources in
+/// precedence order, the entry that was used and the file of each mapping.
 ///
-/// This output includes the printer name, serial number, toner levels,
-/// and total impression metrics in a comma-separated format.
-fn render_csv(printer: &Printer) {
-    let printer_name = printer.name.clone();
-    let header = "Name,Serial,Black_Toner,Cyan_Toner,Magenta_Toner,Yellow_Toner,Total_Impressions";
+/// Written to stderr, so that JSON and CSV output stay parseable.
+pub fn show_data_explanation(printer: &Printer, sources: &[DataSource]) {
+    eprintln!("--> {}\n", "Data sources:".bright_white().bold());
 
-    let serial = printer.serial_number.as_deref().unwrap_or("N/A");
-    let bt = printer
-        .toners
-        .black_toner
-        .as_ref()
-        .and_then(|t| t.level_percent)
-        .map(|v| v.to_string())
-        .unwrap_or_default();
-    let ct = printer
-        .toners
-        .cyan_toner
-        .as_ref()
-        .and_then(|t| t.level_percent)
-        .map(|v| v.to_string())
-        .unwrap_or_default();
-    let mt = printer
-        .toners
-        .magenta_toner
-        .as_ref()
-        .and_then(|t| t.level_percent)
-        .map(|v| v.to_string())
-        .unwrap_or_default();
-    let yt = printer
-        .toners
-        .yellow_toner
-        .as_ref()
-        .and_then(|t| t.level_percent)
-        .map(|v| v.to_string())
+    for (rank, source) in sources.iter().enumerate() {
+        let missing = if source.exists() { "" } else { " (not found)" };
+        eprintln!("{}. {source}{}", rank + 1, missing.dimmed());
+    }
+
+    eprintln!();
+
+    let Some(entry) = &printer.data_source else {
+        eprintln!(
+            "{} none, supplies were read without a data file",
+            "Entry:".bright_cyan().bold()
+        );
+        return;
+    };
+
+    eprintln!("{} {entry}\n", "Entry:".bright_cyan().bold());
+
+    let width = printer
+        .data_mappings
+        .iter()
+        .map(|m| m.path.len())
+        .max()
         .unwrap_or_default();
 
+    for mapping in &printer.data_mappings {
+        eprintln!(
+            "{:<width$}  {}  {}",
+            mapping.path,
+            mapping.value,
+            mapping.file.dimmed()
+        );
+    }
+
+    eprintln!();
+}
+
+/// Display the problems found by `data validate` in the given sources.
+pub fn show_validation(sources: &[DataSource], problems: &[Problem]) {
+    println!("--> {}\n", "Data sources:".bright_white().bold());
+
+    for source in sources {
+        println!("- {source}");
+    }
+
+    println!();
+
+    for problem in problems {
+        println!("{} {problem}", "✗".bright_red().bold());
+    }
+
+    if problems.is_empty() {
+        println!("{}", "✓ No problems found".bright_green().bold());
+    } else {
+        println!();
+    }
+}
+
+/// Colours a status text: green when the printer is healthy, yellow when it reports
+/// errors or a warning, red when it is down.
+fn status_label(printer: &Printer, label: String) -> colored::ColoredString {
+    match printer.device_status {
+        Some(DeviceStatus::Down) => label.bright_red().bold(),
+        Some(DeviceStatus::Warning) => label.bright_yellow().bold(),
+        _ if !printer.errors.is_empty() => label.bright_yellow().bold(),
+        _ => label.bright_green(),
+    }
+}
+
+/// Formats an alert as `[Severity] Code: message (who should act)`, coloured by severity.
+fn alert_line(alert: &PrinterAlert) -> String {
+    let severity = format!("[{}]", alert.severity);
+    let severity = match alert.severity {
+        AlertSeverity::Critical => severity.bright_red().bold(),
+        AlertSeverity::Warning | AlertSeverity::WarningBinaryChangeEvent => {
+            severity.bright_yellow().bold()
+        }
+        AlertSeverity::Other => severity.dimmed(),
+    };
+
+    let message = alert.message();
+    let code = alert.code.to_string();
+
+    let text = if message.eq_ignore_ascii_case(&code) {
+        message
+    } else {
+        format!("{code}: {message}")
+    };
+
+    format!(
+        "{severity} {text} {}",
+        format!("({})", alert.training_level).dimmed()
+    )
+}
+
+/// Note shown next to a tray bar: the loaded media, followed by the tray status when it
+/// is not simply ready, e.g. `A4 Plain` or `Letter, Unavailable, Offline`.
+fn tray_note(tray: &Tray) -> String {
+    let mut parts: Vec<String> = Vec::new();
+
+    let media = [tray.media_size.as_deref(), tray.media_name.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if !media.is_empty() {
+        parts.push(media);
+    }
+
+    let status = tray.status;
+    if status.availability != Availability::Idle || status.offline || status.critical {
+        parts.push(status.to_string());
+    }
+
+    parts.join(", ")
+}
+
+/// Note shown next to an output bin bar: `full` when no space is left, followed by the
+/// bin status when it is not simply ready.
+fn output_note(bin: &OutputBin) -> String {
+    let mut parts: Vec<String> = Vec::new();
+
+    if bin.is_full() {
+        parts.push("full".to_string());
+    } else if bin.remaining_percent.is_some() {
+        parts.push("free".to_string());
+    }
+
+    if let Some(status) = bin.status
+        && (status.availability != Availability::Idle || status.offline || status.critical)
+    {
+        parts.push(status.to_string());
+    }
+
+    parts.join(", ")
+}
+
+/// Label of a toner or drum bar: its colour, or its description when the colour is unknown.
+fn color_label(supply: &Supply) -> String {
+    supply
+        .color
+        .clone()
+        .or_else(|| supply.description.clone())
+        .unwrap_or_else(|| supply.label())
+}
+
+/// Picks the bar colour of a supply from its class.
+///
+/// A receptacle reports the space left, so a low level means it is nearly full; a
+/// consumed supply reports the amount left, so a low level means it is nearly empty.
+/// Either way a low level is bad, but a receptacle with room to spare is shown green.
+fn class_bar_color(supply: &Supply) -> &'static str {
+    match supply.state.percent() {
+        Some(percent) if percent <= 10 => "red",
+        Some(percent) if percent <= 25 => "yellow",
+        Some(_) if supply.is_receptacle() => "green",
+        _ => "white",
+    }
+}
+
+/// Picks the label and bar colours of a supply from its colour name.
+///
+/// Light and gray variants get a dimmed bar, so e.g. light cyan can be told apart from
+/// cyan on the same printer.
+fn bar_colors(supply: &Supply) -> (&'static str, &'static str) {
+    let color = supply.color.as_deref().unwrap_or_default().to_lowercase();
+
+    [
+        ("light cyan", ("bright_cyan", "cyan.dim")),
+        ("light magenta", ("bright_magenta", "magenta.dim")),
+        ("light gray", ("bright_white", "white.dim")),
+        ("gray", ("bright_white", "white.dim")),
+        ("cyan", ("bright_cyan", "cyan")),
+        ("magenta", ("bright_magenta", "magenta")),
+        ("yellow", ("bright_yellow", "yellow")),
+        ("red", ("bright_red", "red")),
+        ("orange", ("bright_red", "red")),
+        ("green", ("bright_green", "green")),
+        ("blue", ("bright_blue", "blue")),
+        ("violet", ("bright_blue", "blue")),
+    ]
+    .into_iter()
+    .find(|(name, _)| color.contains(name))
+    .map_or(("bright_white", "white"), |(_, colors)| colors)
+}
+
+/// Identity columns of the CSV output, written after the level columns.
+const CSV_IDENTITY_COLUMNS: [&str; 8] = [
+    "System_Name",
+    "Location",
+    "Contact",
+    "Firmware",
+    "MAC_Address",
+    "Memory_KB",
+    "Uptime_Seconds",
+    "Object_ID",
+];
+
+/// Quotes a CSV text field, doubling embedded quotes as RFC 4180 requires.
+fn csv_text(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "\"\""))
+}
+
+/// Quotes a CSV column name only when it contains a separator, quote or line break, so
+/// plain names such as `Black_Toner` stay as they are.
+fn csv_column(name: &str) -> String {
+    if name.contains([',', '"', '\n', '\r']) {
+        csv_text(name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// The variable columns of a printer's CSV record and their values: one per toner, e.g.
+/// `Black_Toner`, one per paper tray, e.g. `Paper_Tray_1`, then the identity, if collected.
+///
+/// Levels without a known percentage are written as their state name.
+fn csv_fields(printer: &Printer) -> Vec<(String, String)> {
+    let level = |state: SupplyState| match state {
+        SupplyState::Exact(v) => v.to_string(),
+        state => state.name().to_string(),
+    };
+
+    let toners = printer
+        .supplies_of(PrinterSupply::Toner)
+        .map(|t| (t.label().replace(' ', "_"), level(t.state)));
+    let trays = printer.trays.iter().map(|t| {
+        (
+            format!("Paper_{}", t.name.replace(' ', "_")),
+            level(t.state),
+        )
+    });
+
+    let identity = printer.identity.iter().flat_map(|id| {
+        let values = [
+            id.system_name.as_deref().map(csv_text),
+            id.location.as_deref().map(csv_text),
+            id.contact.as_deref().map(csv_text),
+            id.firmware.as_deref().map(csv_text),
+            id.mac_address.clone(),
+            id.memory_kb.map(|v| v.to_string()),
+            id.uptime_seconds.map(|v| v.to_string()),
+            id.object_id.clone(),
+        ];
+
+        CSV_IDENTITY_COLUMNS
+            .into_iter()
+            .zip(values)
+            .map(|(column, value)| (column.to_string(), value.unwrap_or_default()))
+    });
+
+    toners.chain(trays).chain(identity).collect()
+}
+
+/// Lists the variable columns of the CSV output, in order of first appearance, with the
+/// identity columns last.
+fn csv_columns<'a>(printers: impl IntoIterator<Item = &'a Printer>) -> Vec<String> {
+    let mut columns: Vec<String> = Vec::new();
+
+    for printer in printers {
+        for (column, _) in csv_fields(printer) {
+            if !columns.contains(&column) {
+                columns.push(column);
+            }
+        }
+    }
+
+    columns.sort_by_key(|column| CSV_IDENTITY_COLUMNS.contains(&column.as_str()));
+
+    columns
+}
+
+/// Column names of the per-printer CSV record, e.g.
+/// `Name,Serial,Black_Toner,Total_Impressions,Status,Errors`.
+///
+/// Status and errors come last so the earlier columns keep their positions.
+fn csv_header(columns: &[String]) -> String {
+    ["Name", "Serial"]
+        .into_iter()
+        .map(String::from)
+        .chain(columns.iter().map(|column| csv_column(column)))
+        .chain(["Total_Impressions", "Status", "Errors"].map(String::from))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Formats the printer data as a single CSV record, matching [`csv_header`].
+fn csv_record(printer: &Printer, columns: &[String]) -> String {
+    let printer_name = csv_text(&printer.name);
+    let serial = csv_text(printer.serial_number.as_deref().unwrap_or("N/A"));
+    let status = printer.status.map(|s| s.name()).unwrap_or_default();
+    let errors = printer
+        .errors
+        .iter()
+        .map(|e| e.name())
+        .collect::<Vec<_>>()
+        .join(";");
+
+    let fields = csv_fields(printer);
+    let values = columns.iter().map(|column| {
+        fields
+            .iter()
+            .find(|(name, _)| name == column)
+            .map(|(_, value)| value.clone())
+            .unwrap_or_default()
+    });
+
     let total_impr = printer
         .metrics
         .as_ref()
@@ -150,6 +510,310 @@ fn render_csv(printer: &Printer) {
         .map(|v| v.to_string())
         .unwrap_or_default();
 
-    println!("{header}");
-    println!("\"{printer_name}\",\"{serial}\",{bt},{ct},{mt},{yt},{total_impr}");
+    [printer_name, serial]
+        .into_iter()
+        .chain(values)
+        .chain([total_impr, status.to_string(), errors])
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Formats and prints the printer data as a CSV record to standard output.
+///
+/// This output includes the printer name, serial number, one column per toner and paper
+/// tray, the identity when collected, total impression metrics, status and errors in a
+/// comma-separated format.
+fn render_csv(printer: &Printer) {
+    let columns = csv_columns([printer]);
+
+    println!("{}", csv_header(&columns));
+    println!("{}", csv_record(printer, &columns));
+}
+
+/// Display the combined results of a fleet query.
+///
+/// Text output is a summary table with one row per printer. JSON and CSV output
+/// emit one record per printer, including the error of any printer that failed.
+pub fn show_fleet_values(records: &[FleetRecord], metrics: bool, output: &OutputFormat) {
+    match output {
+        OutputFormat::Json => match serde_json::to_string_pretty(records) {
+            Ok(json) => println!("{json}"),
+            Err(e) => eprintln!("Error generating JSON output: {e}"),
+        },
+        OutputFormat::Csv => render_fleet_csv(records),
+        // Plugin output is produced by the Nagios check itself, so fall back to the table.
+        OutputFormat::Text | OutputFormat::Nagios => render_fleet_table(records, metrics),
+    }
+}
+
+/// Prints the fleet as CSV, prefixing each printer record with its alias and host.
+fn render_fleet_csv(records: &[FleetRecord]) {
+    let columns = csv_columns(records.iter().filter_map(|r| r.printer.as_ref()));
+    // Name, Serial, the variable columns, Total_Impressions, Status and Errors are left
+    // empty for failed printers
+    let empty = ",".repeat(columns.len() + 5);
+
+    println!("Alias,Host,{},Error", csv_header(&columns));
+
+    for record in records {
+        let alias = csv_text(&record.alias);
+        let host = csv_text(&record.host);
+
+        match (&record.printer, &record.error) {
+            (Some(printer), _) => {
+                println!("{alias},{host},{},", csv_record(printer, &columns))
+            }
+            (None, error) => {
+                let error = csv_text(error.as_deref().unwrap_or_default());
+                println!("{alias},{host},{empty}{error}");
+            }
+        }
+    }
+}
+
+/// Prints the fleet as a summary table of toner levels.
+fn render_fleet_table(records: &[FleetRecord], metrics: bool) {
+    let percent = |toner: &Option<Toner>| {
+        toner
+            .as_ref()
+            .map(|t| t.state.to_string())
+            .unwrap_or_else(|| "-".to_string())
+    };
+
+    let sep = "│".dimmed();
+
+    print!(
+        " {:<16} {sep} {:<15} {sep} {:<10} {sep} {:>5} {sep} {:>5} {sep} {:>7} {sep} {:>6}",
+        "Alias".bold(),
+        "Host".bold(),
+        "Status".bold(),
+        "Black".bold(),
+        "Cyan".bold(),
+        "Magenta".bold(),
+        "Yellow".bold(),
+    );
+    if metrics {
+        print!(" {sep} {:>9}", "Pages".bold());
+    }
+    println!();
+
+    let width = if metrics { 103 } else { 91 };
+    println!("{}", "─".repeat(width).dimmed());
+
+    for record in records {
+        let alias = format!("{:<16}", record.alias);
+
+        match &record.printer {
+            Some(printer) => {
+                let t = &printer.toners();
+                let status = printer
+                    .status
+                    .map(|s| s.to_string())
+                    .unwrap_or_else(|| "-".to_string());
+                print!(
+                    " {} {sep} {:<15} {sep} {} {sep} {:>5} {sep} {:>5} {sep} {:>7} {sep} {:>6}",
+                    alias.cyan().bold(),
+                    record.host,
+                    status_label(printer, format!("{status:<10}")),
+                    percent(&t.black_toner),
+                    percent(&t.cyan_toner),
+                    percent(&t.magenta_toner),
+                    percent(&t.yellow_toner),
+                );
+                if metrics {
+                    let pages = printer
+                        .metrics
+                        .as_ref()
+                        .and_then(|m| m.total_impressions)
+                        .map(|v| v.to_string())
+                        .unwrap_or_else(|| "-".to_string());
+                    print!(" {sep} {pages:>9}");
+                }
+                println!();
+            }
+            None => {
+                println!(
+                    " {} {sep} {:<15} {sep} {}",
+                    alias.red().bold(),
+                    record.host,
+                    record.error.as_deref().unwrap_or("Unknown error").red()
+                );
+            }
+        }
+    }
+
+    let failed = records.iter().filter(|r| r.printer.is_none()).count();
+    println!(
+        "\n{} {} printers, {} failed",
+        "Summary:".bright_cyan().bold(),
+        records.len(),
+        failed
+    );
+}
+
+/// Display the recorded readings of a printer and the trend of each supply.
+pub fn show_history(
+    alias: &str,
+    entries: &[HistoryEntry],
+    trends: &[SupplyTrend],
+    output: &OutputFormat,
+) {
+    match output {
+        OutputFormat::Json => {
+            let json = serde_json::json!({
+                "alias": alias,
+                "entries": entries,
+                "trends": trends,
+            });
+
+            match serde_json::to_string_pretty(&json) {
+                Ok(json) => println!("{json}"),
+                Err(e) => eprintln!("Error generating JSON output: {e}"),
+            }
+        }
+        OutputFormat::Csv => render_history_csv(entries),
+        OutputFormat::Text | OutputFormat::Nagios => render_history_table(alias, entries, trends),
+    }
+}
+
+/// Prints one CSV row per supply and reading.
+fn render_history_csv(entries: &[HistoryEntry]) {
+    println!("Timestamp,Alias,Serial,Supply,Level,Max_Level,Level_Percent,Total_Impressions");
+
+    for entry in entries {
+        let alias = csv_text(&entry.alias);
+        let serial = csv_text(entry.serial_number.as_deref().unwrap_or("N/A"));
+        let total = entry
+            .total_impressions
+            .map(|v| v.to_string())
+            .unwrap_or_default();
+
+        for supply in &entry.supplies {
+            let percent = supply
+                .level_percent
+                .map(|v| v.to_string())
+                .unwrap_or_default();
+
+            println!(
+                "{},{alias},{serial},{},{},{},{percent},{total}",
+                entry.timestamp,
+                csv_text(&supply.name),
+                supply.level,
+                supply.max_level
+            );
+        }
+    }
+}
+
+/// Prints the readings as a table with one column per supply, followed by the trends.
+fn render_history_table(alias: &str, entries: &[HistoryEntry], trends: &[SupplyTrend]) {
+    let Some(last) = entries.last() else {
+        println!("No readings recorded for '{alias}'. Run a query with '--record' first.");
+        return;
+    };
+
+    println!(
+        "{} {alias} ({})",
+        "History:".bright_cyan().bold(),
+        last.model
+    );
+    if let Some(serial) = &last.serial_number {
+        println!("{} {serial}", "Serial:".bright_cyan().bold());
+    }
+    println!();
+
+    let sep = "│".dimmed();
+    let names: Vec<&str> = last.supplies.iter().map(|s| s.name.as_str()).collect();
+
+    print!(" {:<19} {sep} {:>9}", "Time".bold(), "Pages".bold());
+    for name in &names {
+        print!(" {sep} {:>w$}", name.bold(), w = name.len().max(5));
+    }
+    println!();
+    println!(
+        "{}",
+        "─"
+            .repeat(34 + names.iter().map(|n| n.len().max(5) + 3).sum::<usize>())
+            .dimmed()
+    );
+
+    for entry in entries {
+        let time = chrono::DateTime::parse_from_rfc3339(&entry.timestamp)
+            .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_else(|_| entry.timestamp.clone());
+        let pages = entry
+            .total_impressions
+            .map(|v| v.to_string())
+            .unwrap_or_else(|| "-".to_string());
+
+        print!(" {time:<19} {sep} {pages:>9}");
+        for name in &names {
+            let percent = entry
+                .percent(name)
+                .map(|v| format!("{v}%"))
+                .unwrap_or_else(|| "-".to_string());
+            print!(" {sep} {percent:>w$}", w = name.len().max(5));
+        }
+        println!();
+    }
+
+    if trends.is_empty() {
+        return;
+    }
+
+    println!("\n--> {}\n", "Trends:".bright_white().bold());
+    for trend in trends {
+        let rate = trend
+            .per_day
+            .map(|r| format!(" ({r:+.2}%/day)"))
+            .unwrap_or_default();
+
+        println!(
+            "{} {}% → {}% ({:+}%){rate}",
+            format!("{}:", trend.name).bright_cyan().bold(),
+            trend.first_percent,
+            trend.last_percent,
+            trend.change
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::printer::supply::{
+        drum::Drums,
+        toner::{Toner, Toners},
+    };
+
+    #[test]
+    fn csv_text_doubles_quotes() {
+        assert_eq!(csv_text("HP \"Color\", Pro"), "\"HP \"\"Color\"\", Pro\"");
+        assert_eq!(csv_column("Black_Toner"), "Black_Toner");
+        assert_eq!(csv_column("Paper_Tray_1,_MP"), "\"Paper_Tray_1,_MP\"");
+    }
+
+    #[test]
+    fn csv_record_quotes_the_name_and_serial() {
+        let mut printer = Printer::new(
+            "Office \"Main\", 2nd floor".to_string(),
+            Some("E,1".to_string()),
+            Toners {
+                black_toner: Some(Toner::new(40, 100, None)),
+                ..Default::default()
+            },
+            Drums::default(),
+            None,
+            None,
+            None,
+        );
+        printer.calculate_all_levels();
+
+        let columns = csv_columns([&printer]);
+
+        assert_eq!(
+            csv_record(&printer, &columns),
+            "\"Office \"\"Main\"\", 2nd floor\",\"E,1\",40,,,"
+        );
+    }
 }
@@ -9,9 +9,11 @@ use theme::CliTheme;
 pub mod args;
 pub mod commands;
 pub mod display;
-mod output;
+pub mod nagios;
+pub mod output;
 pub mod progress;
 mod theme;
+pub mod watch;
 
 /// Structure that holds general parameters for the application.
 ///
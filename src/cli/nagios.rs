@@ -0,0 +1,313 @@
+use crate::{
+    error::{AppError, ErrorKind},
+    fleet::FleetRecord,
+    printer::{Printer, supply::Supply},
+};
+use std::fmt::Display;
+
+/// Service states defined by the Nagios plugin API, in increasing order of severity.
+///
+/// `Unknown` ranks above `Critical` so that a failed query is never hidden by a reading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PluginStatus {
+    Ok,
+    Warning,
+    Critical,
+    Unknown,
+}
+
+impl PluginStatus {
+    /// Process exit code expected by Nagios/Icinga for this state.
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Self::Ok => 0,
+            Self::Warning => 1,
+            Self::Critical => 2,
+            Self::Unknown => 3,
+        }
+    }
+}
+
+impl Display for PluginStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ok => write!(f, "OK"),
+            Self::Warning => write!(f, "WARNING"),
+            Self::Critical => write!(f, "CRITICAL"),
+            Self::Unknown => write!(f, "UNKNOWN"),
+        }
+    }
+}
+
+/// Supply level percentages at or below which a supply is reported.
+#[derive(Debug, Clone, Copy)]
+pub struct Thresholds {
+    pub warning: i64,
+    pub critical: i64,
+}
+
+impl Thresholds {
+    /// Creates thresholds, rejecting levels outside 0-100% and a critical level above
+    /// the warning level.
+    pub fn new(warning: i64, critical: i64) -> Result<Self, AppError> {
+        for (name, level) in [("Warning", warning), ("Critical", critical)] {
+            if !(0..=100).contains(&level) {
+                return Err(AppError::new(ErrorKind::Cli(format!(
+                    "{name} threshold ({level}%) must be between 0 and 100."
+                ))));
+            }
+        }
+
+        if critical > warning {
+            return Err(AppError::new(ErrorKind::Cli(format!(
+                "Critical threshold ({critical}%) must not be above the warning threshold ({warning}%)."
+            ))));
+        }
+
+        Ok(Self { warning, critical })
+    }
+
+    /// Judges a single supply. Supplies without a known percentage are always OK.
+    fn judge(&self, supply: &Supply) -> PluginStatus {
+        match supply.level_percent {
+            Some(percent) if percent <= self.critical => PluginStatus::Critical,
+            Some(percent) if percent <= self.warning => PluginStatus::Warning,
+            _ => PluginStatus::Ok,
+        }
+    }
+}
+
+/// The result of a check: its overall state, the plugin output and the perfdata.
+pub struct CheckResult {
+    pub status: PluginStatus,
+    pub summary: String,
+    pub details: Vec<String>,
+    pub perfdata: Vec<String>,
+}
+
+impl Display for CheckResult {
+    /// Formats the result as plugin output: a status line, optional long output lines,
+    /// and the perfdata after the `|` separator.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "INKCHECK {} - {}", self.status, self.summary)?;
+
+        if !self.perfdata.is_empty() {
+            write!(f, " | {}", self.perfdata.join(" "))?;
+        }
+
+        for line in &self.details {
+            write!(f, "\n{line}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats a supply as a perfdata item: `'label'=value%;warn;crit;0;100`.
+fn perfdata(label: &str, supply: &Supply, thresholds: &Thresholds) -> Option<String> {
+    let percent = supply.level_percent?;
+    let label = label.replace('\'', "");
+
+    Some(format!(
+        "'{label}'={percent}%;{};{};0;100",
+        thresholds.warning, thresholds.critical
+    ))
+}
+
+/// Judges every supply of a printer and lists the ones that need attention.
+fn judge_printer(printer: &Printer, thresholds: &Thresholds) -> (PluginStatus, Vec<String>) {
+    let mut status = PluginStatus::Ok;
+    let mut problems = Vec::new();
+
+    for supply in &printer.supplies {
+        let supply_status = thresholds.judge(supply);
+
+        if supply_status != PluginStatus::Ok {
+            problems.push(format!("{} {}", supply.label(), supply.state));
+        }
+
+        status = status.max(supply_status);
+    }
+
+    (status, problems)
+}
+
+/// Checks the supply levels of a single printer.
+pub fn check_printer(printer: &Printer, thresholds: &Thresholds) -> CheckResult {
+    let (status, problems) = judge_printer(printer, thresholds);
+
+    let name = match printer.status_summary() {
+        Some(status) => format!("{} ({status})", printer.name),
+        None => printer.name.clone(),
+    };
+
+    let summary = if problems.is_empty() {
+        format!("{name}: all supplies OK")
+    } else {
+        format!("{name}: {}", problems.join(", "))
+    };
+
+    let perfdata = printer
+        .supplies
+        .iter()
+        .filter_map(|s| perfdata(&s.label(), s, thresholds))
+        .collect();
+
+    CheckResult {
+        status,
+        summary,
+        details: Vec::new(),
+        perfdata,
+    }
+}
+
+/// Checks every printer of a fleet query. The worst printer determines the overall state,
+/// and each printer gets its own long output line.
+pub fn check_fleet(records: &[FleetRecord], thresholds: &Thresholds) -> CheckResult {
+    let mut status = PluginStatus::Ok;
+    let mut attention = 0;
+    let mut details = Vec::new();
+    let mut perf = Vec::new();
+
+    for record in records {
+        let (printer_status, line) = match &record.printer {
+            Some(printer) => {
+                let (printer_status, problems) = judge_printer(printer, thresholds);
+
+                perf.extend(printer.supplies.iter().filter_map(|s| {
+                    perfdata(&format!("{} {}", record.alias, s.label()), s, thresholds)
+                }));
+
+                let line = if problems.is_empty() {
+                    "all supplies OK".to_string()
+                } else {
+                    problems.join(", ")
+                };
+
+                (printer_status, line)
+            }
+            None => (
+                PluginStatus::Unknown,
+                record.error.clone().unwrap_or_default(),
+            ),
+        };
+
+        if printer_status != PluginStatus::Ok {
+            attention += 1;
+        }
+
+        status = status.max(printer_status);
+        details.push(format!("[{printer_status}] {}: {line}", record.alias));
+    }
+
+    let summary = if attention == 0 {
+        format!("{} printers, all supplies OK", records.len())
+    } else {
+        format!("{attention} of {} printers need attention", records.len())
+    };
+
+    CheckResult {
+        status,
+        summary,
+        details,
+        perfdata: perf,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::printer::supply::{
+        drum::Drums,
+        fuser::Fuser,
+        toner::{Toner, Toners},
+    };
+
+    fn printer(black: i64, cyan: i64) -> Printer {
+        let mut printer = Printer::new(
+            "Test Printer".to_string(),
+            None,
+            Toners {
+                black_toner: Some(Toner::new(black, 100, None)),
+                cyan_toner: Some(Toner::new(cyan, 100, None)),
+                ..Default::default()
+            },
+            Drums::default(),
+            Some(Fuser::new(90, 100, None)),
+            None,
+            None,
+        );
+        printer.calculate_all_levels();
+        printer
+    }
+
+    fn thresholds() -> Thresholds {
+        Thresholds::new(20, 10).unwrap()
+    }
+
+    #[test]
+    fn healthy_printer_is_ok() {
+        let result = check_printer(&printer(80, 50), &thresholds());
+
+        assert_eq!(result.status, PluginStatus::Ok);
+        assert_eq!(
+            result.to_string(),
+            "INKCHECK OK - Test Printer: all supplies OK | 'Black Toner'=80%;20;10;0;100 'Cyan Toner'=50%;20;10;0;100 'Fuser'=90%;20;10;0;100"
+        );
+    }
+
+    #[test]
+    fn worst_supply_sets_status() {
+        let warning = check_printer(&printer(80, 20), &thresholds());
+        assert_eq!(warning.status, PluginStatus::Warning);
+        assert_eq!(warning.summary, "Test Printer: Cyan Toner 20%");
+
+        let critical = check_printer(&printer(5, 20), &thresholds());
+        assert_eq!(critical.status, PluginStatus::Critical);
+        assert_eq!(
+            critical.summary,
+            "Test Printer: Black Toner 5%, Cyan Toner 20%"
+        );
+    }
+
+    #[test]
+    fn failed_fleet_member_is_unknown() {
+        let records = vec![
+            FleetRecord {
+                alias: "office".to_string(),
+                host: "10.0.0.5".to_string(),
+                printer: Some(printer(5, 50)),
+                error: None,
+            },
+            FleetRecord {
+                alias: "lab".to_string(),
+                host: "10.0.0.6".to_string(),
+                printer: None,
+                error: Some("timeout".to_string()),
+            },
+        ];
+
+        let result = check_fleet(&records, &thresholds());
+
+        assert_eq!(result.status, PluginStatus::Unknown);
+        assert_eq!(result.summary, "2 of 2 printers need attention");
+        assert_eq!(result.details[1], "[UNKNOWN] lab: timeout");
+        assert!(
+            result
+                .perfdata
+                .contains(&"'office Black Toner'=5%;20;10;0;100".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_inverted_thresholds() {
+        assert!(Thresholds::new(10, 20).is_err());
+    }
+
+    #[test]
+    fn rejects_thresholds_outside_percent_range() {
+        assert!(Thresholds::new(150, 10).is_err());
+        assert!(Thresholds::new(20, -5).is_err());
+        assert!(Thresholds::new(100, 0).is_ok());
+    }
+}
@@ -6,12 +6,14 @@ use std::fmt::Display;
 /// - `Text`: Human-readable plain text (Default).
 /// - `Json`: Machine-readable JSON format for integration with other tools.
 /// - `Csv`: Comma-separated values for spreadsheets and data processing.
+/// - `Nagios`: Nagios/Icinga plugin output, with an exit code reflecting supply health.
 #[derive(Debug, Clone, ValueEnum, Default)]
 pub enum OutputFormat {
     #[default]
     Text,
     Json,
     Csv,
+    Nagios,
 }
 
 impl Display for OutputFormat {
@@ -20,6 +22,7 @@ impl Display for OutputFormat {
             Self::Text => write!(f, "text"),
             Self::Json => write!(f, "json"),
             Self::Csv => write!(f, "csv"),
+            Self::Nagios => write!(f, "nagios"),
         }
     }
 }
@@ -1,12 +1,25 @@
 use super::theme::CliTheme;
+use crate::printer::supply::SupplyState;
 use indicatif::{ProgressBar, ProgressStyle};
 
-/// Renders a static progress bar to the terminal representing a specific percentage level.
+/// Renders a static progress bar to the terminal representing the state of a supply.
 ///
-/// The bar is customized with a label, colors, and a character theme.
-pub fn show_progress(label: &str, label_color: &str, level: u8, bar_color: &str, theme: CliTheme) {
-    let template =
-        format!("{{prefix:>12.{label_color}.bold}} [{{bar:25.{bar_color}}}] {{pos:>3}}%");
+/// The bar is customized with a label, colors, and a character theme. Supplies without a
+/// known percentage show an empty bar followed by `OK` or `?`. A non-empty `note` is
+/// shown dimmed after the value.
+pub fn show_progress(
+    label: &str,
+    label_color: &str,
+    state: SupplyState,
+    bar_color: &str,
+    theme: CliTheme,
+    note: &str,
+) {
+    let value = state.to_string();
+    let template = format!(
+        "{{prefix:>12.{label_color}.bold}} [{{bar:25.{bar_color}}}] {value:>4}  {{msg:.dim}}"
+    );
+    let level = state.percent().unwrap_or(0).clamp(0, 100);
 
     let pb = ProgressBar::new(100);
 
@@ -17,6 +30,7 @@ pub fn show_progress(label: &str, label_color: &str, level: u8, bar_color: &str,
     pb.set_style(style);
     pb.set_prefix(label.to_string());
     pb.set_position(level as u64);
+    pb.set_message(note.to_string());
 
     pb.abandon();
 }
@@ -0,0 +1,161 @@
+use crate::{
+    cli::{CliParams, display::show_printer_values, output::OutputFormat},
+    error::AppError,
+    printer::{Metrics, Printer},
+    snmp::{self, client::SnmpClient},
+};
+use colored::Colorize;
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+
+/// Clears the terminal and moves the cursor to the top-left corner.
+const CLEAR_SCREEN: &str = "\x1B[2J\x1B[H";
+
+/// The values compared between two polls.
+#[derive(Debug, Default)]
+struct Snapshot {
+    supplies: Vec<(String, i64)>,
+    metrics: Option<Metrics>,
+}
+
+impl Snapshot {
+    fn new(printer: &Printer) -> Self {
+        Self {
+            supplies: printer
+                .supplies
+                .iter()
+                .filter_map(|s| s.level_percent.map(|p| (s.label(), p)))
+                .collect(),
+            metrics: printer.metrics,
+        }
+    }
+}
+
+/// Describes what changed between two polls, e.g. `Black Toner -2%` or `Total +15 pages`.
+fn deltas(previous: &Snapshot, current: &Snapshot) -> Vec<String> {
+    let mut changes = Vec::new();
+
+    for (label, percent) in &current.supplies {
+        if let Some((_, before)) = previous.supplies.iter().find(|(l, _)| l == label)
+            && before != percent
+        {
+            changes.push(format!("{label} {:+}%", percent - before));
+        }
+    }
+
+    if let (Some(before), Some(now)) = (&previous.metrics, &current.metrics) {
+        for (label, before, now) in [
+            ("Total", before.total_impressions, now.total_impressions),
+            ("Mono", before.mono_impressions, now.mono_impressions),
+            ("Color", before.color_impressions, now.color_impressions),
+        ] {
+            if let (Some(before), Some(now)) = (before, now)
+                && before != now
+            {
+                changes.push(format!("{label} {:+} pages", now - before));
+            }
+        }
+    }
+
+    changes
+}
+
+/// Re-polls the printer every `interval` and redraws its supply levels in place.
+///
+/// Text output is redrawn together with the changes since the previous poll and the time
+/// of the last update. Other formats print one record per poll. A failed poll is reported
+/// and retried on the next tick instead of ending the watch.
+pub async fn watch(
+    client: &SnmpClient,
+    interval: Duration,
+    app: &CliParams,
+) -> Result<(), AppError> {
+    let params = client.params();
+    let mut ticker = tokio::time::interval(interval);
+    // A poll slower than the interval must not be followed by a burst of catch-up polls
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let mut previous: Option<Snapshot> = None;
+
+    loop {
+        ticker.tick().await;
+
+        let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
+
+        let printer = match snmp::get_printer_values(client).await {
+            Ok(printer) => printer,
+            Err(e) => {
+                eprintln!("{} {e}", format!("[{now}] Poll failed:").red().bold());
+                continue;
+            }
+        };
+
+        let current = Snapshot::new(&printer);
+
+        if let OutputFormat::Text = app.output {
+            print!("{CLEAR_SCREEN}");
+        }
+
+        show_printer_values(
+            printer,
+            params.extra_supplies,
+            params.metrics,
+            params.paper,
+            params.info,
+            &app.theme,
+            &app.output,
+        );
+
+        if let OutputFormat::Text = app.output {
+            let changes = previous
+                .as_ref()
+                .map(|previous| deltas(previous, &current))
+                .unwrap_or_default();
+
+            if !changes.is_empty() {
+                println!("{} {}", "Changes:".bright_cyan().bold(), changes.join(", "));
+            }
+
+            println!(
+                "{} {now} (every {}s, Ctrl+C to stop)",
+                "Last update:".bright_cyan().bold(),
+                interval.as_secs()
+            );
+        }
+
+        previous = Some(current);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(black: i64, total: i64) -> Snapshot {
+        Snapshot {
+            supplies: vec![("Black Toner".to_string(), black)],
+            metrics: Some(Metrics {
+                total_impressions: Some(total),
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn reports_changed_levels_and_counters() {
+        let changes = deltas(&snapshot(50, 1000), &snapshot(48, 1015));
+
+        assert_eq!(changes, vec!["Black Toner -2%", "Total +15 pages"]);
+    }
+
+    #[test]
+    fn unchanged_values_are_omitted() {
+        assert!(deltas(&snapshot(50, 1000), &snapshot(50, 1000)).is_empty());
+    }
+
+    #[test]
+    fn new_supply_has_no_delta() {
+        let previous = Snapshot::default();
+
+        assert!(deltas(&previous, &snapshot(100, 0)).is_empty());
+    }
+}
@@ -31,8 +31,11 @@ pub struct PrinterConfig {
     pub privacy_protocol: Option<String>,
     pub extra_supplies: Option<bool>,
     pub metrics: Option<bool>,
+    pub paper: Option<bool>,
+    pub info: Option<bool>,
     pub timeout: Option<u64>,
     pub retries: Option<u8>,
+    pub history: Option<bool>,
 }
 
 impl Config {
@@ -136,6 +139,14 @@ pub fn apply_config_to_args(args: &mut Args, config: &PrinterConfig) {
         args.metrics = metrics;
     }
 
+    if let Some(paper) = config.paper {
+        args.paper = paper;
+    }
+
+    if let Some(info) = config.info {
+        args.info = info;
+    }
+
     if let Some(timeout) = config.timeout {
         args.timeout = timeout;
     }
@@ -143,6 +154,10 @@ pub fn apply_config_to_args(args: &mut Args, config: &PrinterConfig) {
     if let Some(retries) = config.retries {
         args.retries = retries;
     }
+
+    if let Some(history) = config.history {
+        args.record = history;
+    }
 }
 
 /// Resolves the configuration path based on the OS standard.
@@ -9,7 +9,7 @@ use crate::{
             toner::{Toner, Toners},
         },
     },
-    snmp::{SnmpClientParams, value::get_snmp_value},
+    snmp::client::SnmpClient,
 };
 use async_trait::async_trait;
 
@@ -25,6 +25,15 @@ const YELLOW_DRUM_CODE: u8 = 0x7b;
 
 const FUSER_CODE: u8 = 0x6a;
 
+/// prtGeneralSerialNumber.
+const PRT_GENERAL_SERIAL_NUMBER: &[u64] = &[1, 3, 6, 1, 2, 1, 43, 5, 1, 1, 17, 1];
+
+/// Maintenance binary blob (Standard).
+const BR_INFO_MAINTENANCE: &[u64] = &[1, 3, 6, 1, 4, 1, 2435, 2, 3, 9, 4, 2, 1, 5, 5, 8, 0];
+
+/// brInfoNextCare (Legacy).
+const BR_INFO_NEXT_CARE_LEGACY: &[u64] = &[1, 3, 6, 1, 4, 1, 2435, 2, 3, 9, 4, 2, 1, 5, 5, 11, 0];
+
 /// Scans a raw byte slice for a specific supply code pattern to extract its remaining percentage.
 fn find_value_in_brother_bytes(bytes: &[u8], code: u8) -> Option<i64> {
     let pattern = [code, 0x01, 0x04];
@@ -44,28 +53,13 @@ fn find_value_in_brother_bytes(bytes: &[u8], code: u8) -> Option<i64> {
 pub struct BrotherDriver;
 
 impl BrotherDriver {
-    /// Fetches the serial number via SNMP.
-    async fn fetch_serial(&self, params: &SnmpClientParams) -> Result<Option<String>, AppError> {
-        let oid = &[1, 3, 6, 1, 2, 1, 43, 5, 1, 1, 17, 1];
-        Ok(Some(get_snmp_value(oid, params).await?))
-    }
-
-    /// Determines the correct OID based on the model and fetches the maintenance binary blob.
-    async fn fetch_maintenance_data(
-        &self,
-        params: &SnmpClientParams,
-        printer_name: &str,
-    ) -> Result<Vec<u8>, AppError> {
-        let old_model = printer_name.contains("HL-5350DN");
-
-        // Select OID based on model generation
-        let oid = if old_model {
-            &[1, 3, 6, 1, 4, 1, 2435, 2, 3, 9, 4, 2, 1, 5, 5, 11, 0] // brInfoNextCare (Legacy)
+    /// Selects the maintenance data OID based on the model generation.
+    fn maintenance_oid(&self, printer_name: &str) -> &'static [u64] {
+        if printer_name.contains("HL-5350DN") {
+            BR_INFO_NEXT_CARE_LEGACY
         } else {
-            &[1, 3, 6, 1, 4, 1, 2435, 2, 3, 9, 4, 2, 1, 5, 5, 8, 0] // Standard
-        };
-
-        get_snmp_value::<Vec<u8>>(oid, params).await
+            BR_INFO_MAINTENANCE
+        }
     }
 
     /// Parses the binary blob to extract Toner levels.
@@ -108,14 +102,18 @@ impl PrinterDriver for BrotherDriver {
 
     async fn get_supplies(
         &self,
-        params: &SnmpClientParams,
+        client: &SnmpClient,
         printer_name: &str,
     ) -> Result<Printer, AppError> {
-        // Fetch Serial Number
-        let serial_number = self.fetch_serial(params).await?;
+        let maintenance_oid = self.maintenance_oid(printer_name);
+
+        // Fetch Serial Number and Maintenance Data in a single request
+        let values = client
+            .get_many(&[PRT_GENERAL_SERIAL_NUMBER.to_vec(), maintenance_oid.to_vec()])
+            .await?;
 
-        // Fetch Maintenance Data
-        let bytes = self.fetch_maintenance_data(params, printer_name).await?;
+        let serial_number = Some(values.get::<String>(PRT_GENERAL_SERIAL_NUMBER)?);
+        let bytes = values.get::<Vec<u8>>(maintenance_oid)?;
 
         // Parse Supplies
         let toners = self.extract_toners(&bytes);
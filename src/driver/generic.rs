@@ -1,248 +1,294 @@
 use crate::{
-    error::{AppError, ErrorKind},
+    error::AppError,
     printer::{
         Metrics, Printer,
         driver::PrinterDriver,
         load::load_printer,
+        output::OutputBin,
+        schema::{ModelDefinition, OutputDefinition, SupplyDefinition},
+        status::SubunitStatus,
         supply::{
-            PrinterSupply,
             drum::{Drum, Drums},
             fuser::Fuser,
             reservoir::Reservoir,
             toner::{Toner, TonerColor, Toners},
         },
+        transform::{ValueDefinition, ValueReader},
     },
-    snmp::{SnmpClientParams, value::get_snmp_value},
+    snmp::{SnmpClientParams, client::SnmpClient, get_object_id, value::SnmpValues},
     utils::parse_oid_to_vec,
 };
 use async_trait::async_trait;
-use serde_json::Value;
+
+/// The standard CMYK slots, in the order they are stored in [`Toners`] and [`Drums`].
+const CMYK: [TonerColor; 4] = [
+    TonerColor::Black,
+    TonerColor::Cyan,
+    TonerColor::Magenta,
+    TonerColor::Yellow,
+];
+
+/// How to read a supply's current and maximum level.
+struct SupplyReaders {
+    level: ValueReader,
+    max_level: ValueReader,
+}
+
+/// The OIDs of a vendor-mapped output bin.
+struct OutputOids {
+    name: String,
+    remaining_capacity: Vec<u64>,
+    max_capacity: Vec<u64>,
+    status: Option<Vec<u64>>,
+}
+
+/// Every OID needed to build a [`Printer`], resolved from its JSON mapping up front
+/// so that all of them can be fetched in a single batch.
+#[derive(Default)]
+struct QueryPlan {
+    serial_number: Option<Vec<u64>>,
+    toners: [Option<SupplyReaders>; 4],
+    drums: [Option<SupplyReaders>; 4],
+    fuser: Option<SupplyReaders>,
+    reservoir: Option<SupplyReaders>,
+    outputs: Vec<OutputOids>,
+    total_impressions: Option<ValueReader>,
+    mono_impressions: Option<ValueReader>,
+    color_impressions: Option<ValueReader>,
+}
+
+impl QueryPlan {
+    /// Collects every OID referenced by the plan.
+    fn oids(&self) -> Vec<Vec<u64>> {
+        let supplies = self
+            .toners
+            .iter()
+            .chain(&self.drums)
+            .chain([&self.fuser, &self.reservoir])
+            .flatten()
+            .flat_map(|s| [&s.level, &s.max_level])
+            .chain(
+                [
+                    &self.total_impressions,
+                    &self.mono_impressions,
+                    &self.color_impressions,
+                ]
+                .into_iter()
+                .flatten(),
+            )
+            .flat_map(|reader| reader.oids().to_vec());
+
+        let outputs = self.outputs.iter().flat_map(|o| {
+            [o.remaining_capacity.clone(), o.max_capacity.clone()]
+                .into_iter()
+                .chain(o.status.clone())
+        });
+
+        supplies
+            .chain(outputs)
+            .chain(self.serial_number.clone())
+            .collect()
+    }
+}
 
 /// A catch-all driver that uses external JSON configuration files to map SNMP OIDs to printer supplies.
 pub struct GenericDriver;
 
 impl GenericDriver {
-    /// Generic helper to fetch specific supply levels.
+    /// Resolves how to read the level and max level of a supply from its definition.
     ///
-    /// Parses the JSON config for OIDs and queries the SNMP agent.
-    async fn fetch_single_supply<T>(
+    /// Returns [`None`] if either value is missing or an empty OID.
+    fn supply_readers(
         &self,
-        oids: &Value,
-        params: &SnmpClientParams,
-        supply_type: PrinterSupply,
-        color: Option<TonerColor>,
-        constructor: impl Fn(i64, i64) -> T,
-    ) -> Result<Option<T>, AppError> {
-        let get_oid = |key: &str| -> Result<Vec<u64>, AppError> {
-            let section = oids.get(supply_type.to_string().to_lowercase());
+        supply: Option<&SupplyDefinition>,
+    ) -> Result<Option<SupplyReaders>, AppError> {
+        let reader = |value: Option<&ValueDefinition>| match value {
+            Some(value) => ValueReader::new(value),
+            None => Ok(None),
+        };
 
-            let target = if let Some(c) = &color {
-                section.and_then(|t| t.get(c.to_string().to_lowercase()))
-            } else {
-                section
-            };
+        let Some(supply) = supply else {
+            return Ok(None);
+        };
 
-            let oid_str = target.and_then(|b| b.get(key)).and_then(|l| l.as_str());
+        match (
+            reader(supply.level.as_ref())?,
+            reader(supply.max_level.as_ref())?,
+        ) {
+            (Some(level), Some(max_level)) => Ok(Some(SupplyReaders { level, max_level })),
+            _ => Ok(None),
+        }
+    }
 
-            match oid_str {
-                Some(s) if !s.is_empty() => parse_oid_to_vec(s),
-                _ => Ok(Vec::new()),
-            }
-        };
+    /// Resolves the output bins listed under `outputs` in the model definition.
+    ///
+    /// Each entry needs a `name` and the `remaining_capacity` and `max_capacity` OIDs; a
+    /// `status` OID (`prtOutputStatus`) is optional. Entries with empty fields are skipped.
+    fn output_oids(&self, outputs: &[OutputDefinition]) -> Result<Vec<OutputOids>, AppError> {
+        let mut oids = Vec::new();
 
-        let level_oid = get_oid("level")?;
-        let max_level_oid = get_oid("max_level")?;
+        for output in outputs {
+            if output.name.is_empty()
+                || output.remaining_capacity.is_empty()
+                || output.max_capacity.is_empty()
+            {
+                continue;
+            }
 
-        if !level_oid.is_empty() && !max_level_oid.is_empty() {
-            let level = get_snmp_value(&level_oid, params).await?;
-            let max_level = get_snmp_value(&max_level_oid, params).await?;
-            Ok(Some(constructor(level, max_level)))
-        } else {
-            Ok(None)
+            oids.push(OutputOids {
+                name: output.name.clone(),
+                remaining_capacity: parse_oid_to_vec(&output.remaining_capacity)?,
+                max_capacity: parse_oid_to_vec(&output.max_capacity)?,
+                status: output
+                    .status
+                    .as_deref()
+                    .filter(|s| !s.is_empty())
+                    .map(parse_oid_to_vec)
+                    .transpose()?,
+            });
         }
+
+        Ok(oids)
     }
 
-    /// Fetches all toner cartridges (Black, Cyan, Magenta, Yellow).
-    async fn fetch_toners(
+    /// Builds the [`QueryPlan`] for a model, honouring the `extra_supplies`, `metrics` and
+    /// `paper` flags.
+    fn plan(
         &self,
-        oids: &Value,
+        model: &ModelDefinition,
         params: &SnmpClientParams,
-    ) -> Result<Toners, AppError> {
-        let (black, cyan, magenta, yellow) = tokio::try_join!(
-            self.fetch_single_supply(
-                oids,
-                params,
-                PrinterSupply::Toner,
-                Some(TonerColor::Black),
-                |l, m| Toner::new(l, m, None),
-            ),
-            self.fetch_single_supply(
-                oids,
-                params,
-                PrinterSupply::Toner,
-                Some(TonerColor::Cyan),
-                |l, m| Toner::new(l, m, None),
-            ),
-            self.fetch_single_supply(
-                oids,
-                params,
-                PrinterSupply::Toner,
-                Some(TonerColor::Magenta),
-                |l, m| Toner::new(l, m, None),
-            ),
-            self.fetch_single_supply(
-                oids,
-                params,
-                PrinterSupply::Toner,
-                Some(TonerColor::Yellow),
-                |l, m| Toner::new(l, m, None),
-            ),
-        )?;
-        Ok(Toners {
-            black_toner: black,
-            cyan_toner: cyan,
-            magenta_toner: magenta,
-            yellow_toner: yellow,
-        })
+    ) -> Result<QueryPlan, AppError> {
+        let mut plan = QueryPlan::default();
+
+        for (slot, color) in plan.toners.iter_mut().zip(CMYK) {
+            *slot = self.supply_readers(model.toner.as_ref().and_then(|t| t.get(color)))?;
+        }
+
+        if params.extra_supplies {
+            for (slot, color) in plan.drums.iter_mut().zip(CMYK) {
+                *slot = self.supply_readers(model.drum.as_ref().and_then(|d| d.get(color)))?;
+            }
+
+            plan.serial_number = match model.info.as_ref().and_then(|i| i.serial_number.as_ref()) {
+                Some(oid_str) if !oid_str.is_empty() => Some(parse_oid_to_vec(oid_str)?),
+                _ => None,
+            };
+
+            plan.fuser = self.supply_readers(model.fuser.as_ref())?;
+            plan.reservoir = self.supply_readers(model.reservoir.as_ref())?;
+        }
+
+        if params.paper {
+            plan.outputs = self.output_oids(model.outputs.as_deref().unwrap_or_default())?;
+        }
+
+        if params.metrics
+            && let Some(metrics) = &model.metrics
+        {
+            let metric = |value: &Option<ValueDefinition>| match value {
+                Some(value) => ValueReader::new(value),
+                None => Ok(None),
+            };
+
+            plan.total_impressions = metric(&metrics.total_impressions)?;
+            plan.mono_impressions = metric(&metrics.mono_impressions)?;
+            plan.color_impressions = metric(&metrics.color_impressions)?;
+        }
+
+        Ok(plan)
     }
 
-    /// Fetches all drum units if `extra_supplies` is enabled.
-    async fn fetch_drums(
+    /// Builds a supply from the fetched values.
+    fn read_supply<T>(
         &self,
-        oids: &Value,
-        params: &SnmpClientParams,
-    ) -> Result<Drums, AppError> {
-        if !params.extra_supplies {
-            return Ok(Drums::default());
+        values: &SnmpValues,
+        readers: &Option<SupplyReaders>,
+        constructor: impl Fn(i64, i64) -> T,
+    ) -> Result<Option<T>, AppError> {
+        match readers {
+            Some(readers) => {
+                let level = readers.level.read(values)?;
+                let max_level = readers.max_level.read(values)?;
+                Ok(Some(constructor(level, max_level)))
+            }
+            None => Ok(None),
         }
+    }
 
-        let black_drum = self
-            .fetch_single_supply(
-                oids,
-                params,
-                PrinterSupply::Drum,
-                Some(TonerColor::Black),
-                |l, m| Drum::new(l, m, None),
-            )
-            .await?;
-
-        let cyan_drum = self
-            .fetch_single_supply(
-                oids,
-                params,
-                PrinterSupply::Drum,
-                Some(TonerColor::Cyan),
-                |l, m| Drum::new(l, m, None),
-            )
-            .await?;
-
-        let magenta_drum = self
-            .fetch_single_supply(
-                oids,
-                params,
-                PrinterSupply::Drum,
-                Some(TonerColor::Magenta),
-                |l, m| Drum::new(l, m, None),
-            )
-            .await?;
-
-        let yellow_drum = self
-            .fetch_single_supply(
-                oids,
-                params,
-                PrinterSupply::Drum,
-                Some(TonerColor::Yellow),
-                |l, m| Drum::new(l, m, None),
-            )
-            .await?;
+    /// Reads all toner cartridges (Black, Cyan, Magenta, Yellow).
+    fn read_toners(&self, values: &SnmpValues, plan: &QueryPlan) -> Result<Toners, AppError> {
+        let [black, cyan, magenta, yellow] = &plan.toners;
+        let toner = |oids| self.read_supply(values, oids, |l, m| Toner::new(l, m, None));
 
-        Ok(Drums {
-            black_drum,
-            cyan_drum,
-            magenta_drum,
-            yellow_drum,
+        Ok(Toners {
+            black_toner: toner(black)?,
+            cyan_toner: toner(cyan)?,
+            magenta_toner: toner(magenta)?,
+            yellow_toner: toner(yellow)?,
         })
     }
 
-    /// Fetches auxiliary components (Serial Number, Fuser, Reservoir).
-    async fn fetch_extras(
-        &self,
-        oids: &Value,
-        params: &SnmpClientParams,
-    ) -> Result<(Option<String>, Option<Fuser>, Option<Reservoir>), AppError> {
-        if !params.extra_supplies {
-            return Ok((None, None, None));
-        }
+    /// Reads all drum units.
+    fn read_drums(&self, values: &SnmpValues, plan: &QueryPlan) -> Result<Drums, AppError> {
+        let [black, cyan, magenta, yellow] = &plan.drums;
+        let drum = |oids| self.read_supply(values, oids, |l, m| Drum::new(l, m, None));
 
-        // Fetch Serial Number
-        let serial_number = if let Some(oid_str) = oids
-            .get("info")
-            .and_then(|i| i.get("serial_number"))
-            .and_then(|s| s.as_str())
-        {
-            if !oid_str.is_empty() {
-                let oid = parse_oid_to_vec(oid_str)?;
-                Some(get_snmp_value::<String>(&oid, params).await?)
-            } else {
-                None
-            }
-        } else {
-            None
-        };
+        Ok(Drums {
+            black_drum: drum(black)?,
+            cyan_drum: drum(cyan)?,
+            magenta_drum: drum(magenta)?,
+            yellow_drum: drum(yellow)?,
+        })
+    }
 
-        // Fetch Fuser & Reservoir
-        let fuser = self
-            .fetch_single_supply(oids, params, PrinterSupply::Fuser, None, |l, m| {
-                Fuser::new(l, m, None)
-            })
-            .await?;
+    /// Reads the mapped output bins.
+    fn read_outputs(
+        &self,
+        values: &SnmpValues,
+        plan: &QueryPlan,
+    ) -> Result<Vec<OutputBin>, AppError> {
+        plan.outputs
+            .iter()
+            .map(|oids| {
+                let mut bin = OutputBin::new(
+                    oids.name.clone(),
+                    values.get(&oids.remaining_capacity)?,
+                    values.get(&oids.max_capacity)?,
+                );
+                bin.status = oids
+                    .status
+                    .as_ref()
+                    .map(|oid| values.get::<i64>(oid))
+                    .transpose()?
+                    .map(SubunitStatus::from_value);
 
-        let reservoir = self
-            .fetch_single_supply(oids, params, PrinterSupply::Reservoir, None, |l, m| {
-                Reservoir::new(l, m, None)
+                Ok(bin)
             })
-            .await?;
-
-        Ok((serial_number, fuser, reservoir))
+            .collect()
     }
 
-    /// Fetches usage metrics (total, mono, and color impressions).
-    async fn fetch_metrics(
+    /// Reads usage metrics (total, mono, and color impressions).
+    fn read_metrics(
         &self,
-        oids: &Value,
+        values: &SnmpValues,
+        plan: &QueryPlan,
         params: &SnmpClientParams,
     ) -> Result<Option<Metrics>, AppError> {
         if !params.metrics {
             return Ok(None);
         }
 
-        let fetch_metric_oid = |key: &str| -> Option<Vec<u64>> {
-            oids.get("metrics")
-                .and_then(|m| m.get(key))
-                .and_then(|s| s.as_str())
-                .filter(|s| !s.is_empty())
-                .and_then(|s| parse_oid_to_vec(s).ok())
+        let metric = |reader: &Option<ValueReader>| -> Result<Option<i64>, AppError> {
+            reader
+                .as_ref()
+                .map(|reader| reader.read(values))
+                .transpose()
         };
 
-        let mut total = None;
-        if let Some(oid) = fetch_metric_oid("total_impressions") {
-            total = Some(get_snmp_value::<i64>(&oid, params).await?);
-        }
-
-        let mut mono = None;
-        if let Some(oid) = fetch_metric_oid("mono_impressions") {
-            mono = Some(get_snmp_value::<i64>(&oid, params).await?);
-        }
-
-        let mut color = None;
-        if let Some(oid) = fetch_metric_oid("color_impressions") {
-            color = Some(get_snmp_value::<i64>(&oid, params).await?);
-        }
-
         Ok(Some(Metrics {
-            total_impressions: total,
-            mono_impressions: mono,
-            color_impressions: color,
+            total_impressions: metric(&plan.total_impressions)?,
+            mono_impressions: metric(&plan.mono_impressions)?,
+            color_impressions: metric(&plan.color_impressions)?,
         }))
     }
 }
@@ -255,20 +301,33 @@ impl PrinterDriver for GenericDriver {
 
     async fn get_supplies(
         &self,
-        params: &SnmpClientParams,
+        client: &SnmpClient,
         printer_name: &str,
     ) -> Result<Printer, AppError> {
-        let brand = printer_name.split_whitespace().next().ok_or_else(|| {
-            AppError::new(ErrorKind::Parse(
-                "Could not determine printer brand".to_string(),
-            ))
-        })?;
-
-        let oids = load_printer(brand, printer_name, params.data_dir.clone())?;
-        let toners = self.fetch_toners(&oids, params).await?;
-        let drums = self.fetch_drums(&oids, params).await?;
-        let (serial_number, fuser, reservoir) = self.fetch_extras(&oids, params).await?;
-        let metrics = self.fetch_metrics(&oids, params).await?;
+        let params = client.params();
+
+        // Agents without a sysObjectID can still match by description
+        let entry = load_printer(printer_name, params.data_dir.clone(), async || {
+            get_object_id(client).await.ok()
+        })
+        .await?;
+        let plan = self.plan(&entry.definition()?, params)?;
+
+        // Fetch every mapped OID in a single batch
+        let values = client.get_many(&plan.oids()).await?;
+
+        let toners = self.read_toners(&values, &plan)?;
+        let drums = self.read_drums(&values, &plan)?;
+        let serial_number = plan
+            .serial_number
+            .as_ref()
+            .map(|oid| values.get::<String>(oid))
+            .transpose()?;
+        let fuser = self.read_supply(&values, &plan.fuser, |l, m| Fuser::new(l, m, None))?;
+        let reservoir =
+            self.read_supply(&values, &plan.reservoir, |l, m| Reservoir::new(l, m, None))?;
+        let metrics = self.read_metrics(&values, &plan, params)?;
+        let outputs = self.read_outputs(&values, &plan)?;
 
         let mut printer = Printer::new(
             printer_name.to_string(),
@@ -280,6 +339,9 @@ impl PrinterDriver for GenericDriver {
             metrics,
         );
 
+        printer.outputs = outputs;
+        printer.data_source = Some(entry.to_string());
+        printer.data_mappings = entry.mappings();
         printer.calculate_all_levels();
 
         Ok(printer)
@@ -1,2 +1,3 @@
 pub mod brother;
 pub mod generic;
+pub mod printer_mib;
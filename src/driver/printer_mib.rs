@@ -0,0 +1,826 @@
+use crate::{
+    error::{AppError, ErrorKind},
+    printer::{
+        Metrics, Printer,
+        alert::{self, AlertCode, AlertGroup, AlertSeverity, AlertTrainingLevel, PrinterAlert},
+        driver::PrinterDriver,
+        output::OutputBin,
+        status::SubunitStatus,
+        supply::{
+            PrinterSupply, Supply,
+            marker::{SupplyClass, SupplyType},
+        },
+        tray::{self, Tray},
+    },
+    snmp::client::SnmpClient,
+};
+use async_trait::async_trait;
+use std::collections::HashMap;
+
+/// prtMarkerSuppliesEntry (RFC 3805), indexed by `hrDeviceIndex.prtMarkerSuppliesIndex`.
+const PRT_MARKER_SUPPLIES_ENTRY: &[u64] = &[1, 3, 6, 1, 2, 1, 43, 11, 1, 1];
+
+const COL_SUPPLIES_COLORANT_INDEX: u64 = 3;
+const COL_SUPPLIES_CLASS: u64 = 4;
+const COL_SUPPLIES_TYPE: u64 = 5;
+const COL_SUPPLIES_DESCRIPTION: u64 = 6;
+const COL_SUPPLIES_MAX_CAPACITY: u64 = 8;
+const COL_SUPPLIES_LEVEL: u64 = 9;
+
+/// prtMarkerColorantEntry (RFC 3805), indexed by `hrDeviceIndex.prtMarkerColorantIndex`.
+const PRT_MARKER_COLORANT_ENTRY: &[u64] = &[1, 3, 6, 1, 2, 1, 43, 12, 1, 1];
+
+const COL_COLORANT_VALUE: u64 = 4;
+
+/// prtAlertEntry (RFC 3805), indexed by `hrDeviceIndex.prtAlertIndex`.
+const PRT_ALERT_ENTRY: &[u64] = &[1, 3, 6, 1, 2, 1, 43, 18, 1, 1];
+
+const COL_ALERT_SEVERITY_LEVEL: u64 = 2;
+const COL_ALERT_TRAINING_LEVEL: u64 = 3;
+const COL_ALERT_GROUP: u64 = 4;
+const COL_ALERT_GROUP_INDEX: u64 = 5;
+const COL_ALERT_LOCATION: u64 = 6;
+const COL_ALERT_CODE: u64 = 7;
+const COL_ALERT_DESCRIPTION: u64 = 8;
+
+/// prtInputEntry (RFC 3805), indexed by `hrDeviceIndex.prtInputIndex`.
+const PRT_INPUT_ENTRY: &[u64] = &[1, 3, 6, 1, 2, 1, 43, 8, 2, 1];
+
+const COL_INPUT_DIM_UNIT: u64 = 3;
+const COL_INPUT_MEDIA_DIM_FEED_DIR_DECLARED: u64 = 4;
+const COL_INPUT_MEDIA_DIM_XFEED_DIR_DECLARED: u64 = 5;
+const COL_INPUT_MAX_CAPACITY: u64 = 9;
+const COL_INPUT_CURRENT_LEVEL: u64 = 10;
+const COL_INPUT_STATUS: u64 = 11;
+const COL_INPUT_MEDIA_NAME: u64 = 12;
+const COL_INPUT_NAME: u64 = 13;
+const COL_INPUT_DESCRIPTION: u64 = 18;
+
+/// prtOutputEntry (RFC 3805), indexed by `hrDeviceIndex.prtOutputIndex`.
+const PRT_OUTPUT_ENTRY: &[u64] = &[1, 3, 6, 1, 2, 1, 43, 9, 2, 1];
+
+const COL_OUTPUT_MAX_CAPACITY: u64 = 4;
+const COL_OUTPUT_REMAINING_CAPACITY: u64 = 5;
+const COL_OUTPUT_STATUS: u64 = 6;
+const COL_OUTPUT_NAME: u64 = 7;
+const COL_OUTPUT_DESCRIPTION: u64 = 12;
+
+/// prtConsoleDisplayBufferText, indexed by `hrDeviceIndex.prtConsoleDisplayBufferIndex`.
+const PRT_CONSOLE_DISPLAY_BUFFER_TEXT: &[u64] = &[1, 3, 6, 1, 2, 1, 43, 16, 5, 1, 2];
+
+/// prtGeneralSerialNumber for the first printer device.
+const PRT_GENERAL_SERIAL_NUMBER: &[u64] = &[1, 3, 6, 1, 2, 1, 43, 5, 1, 1, 17, 1];
+
+/// prtMarkerLifeCount for the first marker of the first printer device.
+const PRT_MARKER_LIFE_COUNT: &[u64] = &[1, 3, 6, 1, 2, 1, 43, 10, 2, 1, 4, 1, 1];
+
+/// A single row of the `prtMarkerSuppliesTable`.
+#[derive(Debug, Clone)]
+struct SupplyRow {
+    description: String,
+    supply_type: SupplyType,
+    class: SupplyClass,
+    /// `prtMarkerColorantValue` of the colorant the supply refers to, e.g. `cyan`.
+    colorant: Option<String>,
+    max_capacity: i64,
+    level: i64,
+}
+
+/// Colour names recognised in supply descriptions, most specific first so that
+/// e.g. `light cyan` is not reported as `cyan`.
+const COLOR_NAMES: &[(&str, &str)] = &[
+    ("photo black", "Photo Black"),
+    ("matte black", "Matte Black"),
+    ("light black", "Light Black"),
+    ("light cyan", "Light Cyan"),
+    ("light magenta", "Light Magenta"),
+    ("light gray", "Light Gray"),
+    ("light grey", "Light Gray"),
+    ("black", "Black"),
+    ("cyan", "Cyan"),
+    ("magenta", "Magenta"),
+    ("yellow", "Yellow"),
+    ("gray", "Gray"),
+    ("grey", "Gray"),
+    ("white", "White"),
+    ("clear", "Clear"),
+    ("red", "Red"),
+    ("green", "Green"),
+    ("blue", "Blue"),
+    ("orange", "Orange"),
+    ("violet", "Violet"),
+];
+
+/// Infers the colour of a supply from its `prtMarkerSuppliesDescription`.
+fn color_from_description(description: &str) -> Option<&'static str> {
+    let description = description.to_lowercase();
+
+    COLOR_NAMES
+        .iter()
+        .find(|(pattern, _)| description.contains(pattern))
+        .map(|(_, color)| *color)
+}
+
+/// Maps a `prtMarkerColorantValue` to a colour name.
+///
+/// Agents spell the values differently (`lightCyan`, `light-cyan`, `LIGHT CYAN`), so the
+/// value is split into lowercase words first. Unlisted colours are kept in title case;
+/// `other` and `unknown` give [`None`].
+fn color_from_colorant(value: &str) -> Option<String> {
+    let mut words = String::new();
+    let mut previous_lower = false;
+
+    for c in value.trim().chars() {
+        if c == '-' || c == '_' || c.is_whitespace() {
+            words.push(' ');
+        } else {
+            if c.is_uppercase() && previous_lower {
+                words.push(' ');
+            }
+            words.extend(c.to_lowercase());
+        }
+        previous_lower = c.is_lowercase();
+    }
+
+    let words: Vec<&str> = words.split_whitespace().collect();
+    let normalized = words.join(" ");
+
+    if matches!(normalized.as_str(), "" | "other" | "unknown") {
+        return None;
+    }
+
+    let color = color_from_description(&normalized).map(str::to_string);
+
+    Some(color.unwrap_or_else(|| {
+        words
+            .iter()
+            .map(|word| {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }))
+}
+
+/// Builds the `prtMarkerColorantTable` index a supply refers to: the colorant index
+/// within the supply's own device. An index of 0 means the supply has no colorant.
+fn colorant_key(supply_index: &[u64], colorant_index: i64) -> Option<Vec<u64>> {
+    let (_, device) = supply_index.split_last()?;
+
+    (colorant_index > 0).then(|| {
+        let mut key = device.to_vec();
+        key.push(colorant_index as u64);
+        key
+    })
+}
+
+/// Classifies a row into the categories shown by the CLI.
+///
+/// Ink is grouped with the toners so it is judged the same way; it keeps its type and is
+/// named as ink.
+fn supply_kind(row: &SupplyRow) -> PrinterSupply {
+    match row.supply_type {
+        SupplyType::Toner
+        | SupplyType::TonerCartridge
+        | SupplyType::MatteToner
+        | SupplyType::Ink
+        | SupplyType::InkCartridge
+        | SupplyType::MatteInk => PrinterSupply::Toner,
+        SupplyType::Opc => PrinterSupply::Drum,
+        SupplyType::Fuser => PrinterSupply::Fuser,
+        SupplyType::WasteToner | SupplyType::WasteInk => PrinterSupply::Reservoir,
+        // Other receptacles (e.g. collection units) are reported as the reservoir
+        _ if row.class == SupplyClass::Receptacle => PrinterSupply::Reservoir,
+        _ => PrinterSupply::Other,
+    }
+}
+
+/// Maps the reported supply rows onto the [`Printer`] model.
+///
+/// The colour comes from the supply's colorant when the printer reports one, otherwise
+/// from its description. Toners and drums without a recognizable colour are assumed to be
+/// black, which matches how monochrome devices usually describe their single toner and drum.
+fn build_printer(
+    printer_name: &str,
+    serial_number: Option<String>,
+    rows: &[SupplyRow],
+    metrics: Option<Metrics>,
+    extra_supplies: bool,
+) -> Printer {
+    let supplies = rows
+        .iter()
+        .filter_map(|row| {
+            let kind = supply_kind(row);
+            if kind != PrinterSupply::Toner && !extra_supplies {
+                return None;
+            }
+
+            let mut supply = Supply::new(kind, row.level, row.max_capacity, None)
+                .with_type(row.supply_type, row.class)
+                .with_description(row.description.clone());
+
+            let colorant_color = row.colorant.as_deref().and_then(color_from_colorant);
+            let description_color = color_from_description(&row.description).map(str::to_string);
+
+            let color = match (colorant_color.or(description_color), kind) {
+                (Some(color), _) => Some(color),
+                (None, PrinterSupply::Toner | PrinterSupply::Drum) => Some("Black".to_string()),
+                (None, _) => None,
+            };
+
+            if let Some(colorant) = &row.colorant {
+                supply = supply.with_colorant(colorant.clone());
+            }
+
+            Some(match color {
+                Some(color) => supply.with_color(color),
+                None => supply,
+            })
+        })
+        .collect();
+
+    let mut printer =
+        Printer::from_supplies(printer_name.to_string(), serial_number, supplies, metrics);
+
+    printer.calculate_all_levels();
+
+    printer
+}
+
+/// Joins the non-blank display lines, trimming the padding many printers add.
+fn console_message(lines: impl IntoIterator<Item = String>) -> Option<String> {
+    let lines: Vec<String> = lines
+        .into_iter()
+        .map(|line| {
+            line.trim_matches(|c: char| c.is_whitespace() || c == '\0')
+                .to_string()
+        })
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    (!lines.is_empty()).then(|| lines.join(" / "))
+}
+
+/// A standards-based driver that discovers supplies from the RFC 3805 `prtMarkerSuppliesTable`.
+///
+/// Used as the last resort when neither a vendor driver nor a JSON mapping matches the device.
+pub struct PrinterMibDriver;
+
+impl PrinterMibDriver {
+    /// Builds the OID of a table column from the OID of its entry.
+    fn column_oid(entry: &[u64], column: u64) -> Vec<u64> {
+        let mut oid = entry.to_vec();
+        oid.push(column);
+        oid
+    }
+
+    /// Walks a numeric table column into a map keyed by row index.
+    async fn walk_numeric_column(
+        &self,
+        client: &SnmpClient,
+        entry: &[u64],
+        column: u64,
+    ) -> Result<HashMap<Vec<u64>, i64>, AppError> {
+        Ok(client
+            .walk::<i64>(&Self::column_oid(entry, column))
+            .await?
+            .into_iter()
+            .map(|row| (row.index, row.value))
+            .collect())
+    }
+
+    /// Walks the `prtMarkerSuppliesTable` and joins its columns into rows.
+    ///
+    /// The description column drives the row order; rows missing from the other
+    /// columns are skipped. Colorants are optional, since not every agent implements the
+    /// `prtMarkerColorantTable`.
+    async fn fetch_supply_rows(&self, client: &SnmpClient) -> Result<Vec<SupplyRow>, AppError> {
+        let descriptions = client
+            .walk::<String>(&Self::column_oid(
+                PRT_MARKER_SUPPLIES_ENTRY,
+                COL_SUPPLIES_DESCRIPTION,
+            ))
+            .await?;
+
+        if descriptions.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let column = |column| self.walk_numeric_column(client, PRT_MARKER_SUPPLIES_ENTRY, column);
+
+        let types = column(COL_SUPPLIES_TYPE).await?;
+        let classes = column(COL_SUPPLIES_CLASS).await?;
+        let max_capacities = column(COL_SUPPLIES_MAX_CAPACITY).await?;
+        let levels = column(COL_SUPPLIES_LEVEL).await?;
+
+        let colorant_indexes = column(COL_SUPPLIES_COLORANT_INDEX)
+            .await
+            .unwrap_or_default();
+        let colorants = if colorant_indexes.values().any(|index| *index > 0) {
+            self.walk_string_column(client, PRT_MARKER_COLORANT_ENTRY, COL_COLORANT_VALUE)
+                .await
+                .unwrap_or_default()
+        } else {
+            HashMap::new()
+        };
+
+        Ok(descriptions
+            .into_iter()
+            .filter_map(|row| {
+                Some(SupplyRow {
+                    supply_type: SupplyType::from_value(*types.get(&row.index)?),
+                    class: SupplyClass::from_value(*classes.get(&row.index)?),
+                    max_capacity: *max_capacities.get(&row.index)?,
+                    level: *levels.get(&row.index)?,
+                    colorant: colorant_indexes
+                        .get(&row.index)
+                        .and_then(|index| colorant_key(&row.index, *index))
+                        .and_then(|key| colorants.get(&key).cloned()),
+                    description: row.value,
+                })
+            })
+            .collect())
+    }
+
+    /// Walks the `prtAlertTable` into the active alerts of the device.
+    ///
+    /// The severity column drives the row order. Other columns are optional, since some
+    /// agents leave out the ones they do not implement.
+    pub async fn fetch_alerts(&self, client: &SnmpClient) -> Result<Vec<PrinterAlert>, AppError> {
+        let column = |column| self.walk_numeric_column(client, PRT_ALERT_ENTRY, column);
+
+        let severities = client
+            .walk::<i64>(&Self::column_oid(PRT_ALERT_ENTRY, COL_ALERT_SEVERITY_LEVEL))
+            .await?;
+
+        if severities.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let training_levels = column(COL_ALERT_TRAINING_LEVEL).await?;
+        let groups = column(COL_ALERT_GROUP).await?;
+        let group_indexes = column(COL_ALERT_GROUP_INDEX).await?;
+        let locations = column(COL_ALERT_LOCATION).await?;
+        let codes = column(COL_ALERT_CODE).await?;
+        let descriptions: HashMap<Vec<u64>, String> = client
+            .walk::<String>(&Self::column_oid(PRT_ALERT_ENTRY, COL_ALERT_DESCRIPTION))
+            .await?
+            .into_iter()
+            .map(|row| (row.index, row.value))
+            .collect();
+
+        Ok(severities
+            .into_iter()
+            .map(|row| PrinterAlert {
+                severity: AlertSeverity::from_value(row.value),
+                training_level: AlertTrainingLevel::from_value(
+                    training_levels.get(&row.index).copied().unwrap_or_default(),
+                ),
+                group: AlertGroup(groups.get(&row.index).copied().unwrap_or(1)),
+                group_index: group_indexes
+                    .get(&row.index)
+                    .copied()
+                    .and_then(alert::optional_index),
+                location: locations
+                    .get(&row.index)
+                    .copied()
+                    .and_then(alert::optional_index),
+                code: AlertCode(codes.get(&row.index).copied().unwrap_or(2)),
+                description: descriptions.get(&row.index).cloned().unwrap_or_default(),
+            })
+            .collect())
+    }
+
+    /// Walks a string table column into a map keyed by row index, dropping blank values.
+    async fn walk_string_column(
+        &self,
+        client: &SnmpClient,
+        entry: &[u64],
+        column: u64,
+    ) -> Result<HashMap<Vec<u64>, String>, AppError> {
+        Ok(client
+            .walk::<String>(&Self::column_oid(entry, column))
+            .await?
+            .into_iter()
+            .filter(|row| !row.value.trim().is_empty())
+            .map(|row| (row.index, row.value.trim().to_string()))
+            .collect())
+    }
+
+    /// Walks the `prtInputTable` into the paper trays of the device.
+    ///
+    /// The current level column drives the row order. Trays without a name are named
+    /// after their description, or `Tray N` from their row index.
+    pub async fn fetch_trays(&self, client: &SnmpClient) -> Result<Vec<Tray>, AppError> {
+        let column = |column| self.walk_numeric_column(client, PRT_INPUT_ENTRY, column);
+        let text = |column| self.walk_string_column(client, PRT_INPUT_ENTRY, column);
+
+        let levels = client
+            .walk::<i64>(&Self::column_oid(PRT_INPUT_ENTRY, COL_INPUT_CURRENT_LEVEL))
+            .await?;
+
+        if levels.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let max_capacities = column(COL_INPUT_MAX_CAPACITY).await?;
+        let statuses = column(COL_INPUT_STATUS).await?;
+        let dim_units = column(COL_INPUT_DIM_UNIT).await?;
+        let feed_dims = column(COL_INPUT_MEDIA_DIM_FEED_DIR_DECLARED).await?;
+        let cross_feed_dims = column(COL_INPUT_MEDIA_DIM_XFEED_DIR_DECLARED).await?;
+        let media_names = text(COL_INPUT_MEDIA_NAME).await?;
+        let names = text(COL_INPUT_NAME).await?;
+        let descriptions = text(COL_INPUT_DESCRIPTION).await?;
+
+        Ok(levels
+            .into_iter()
+            .map(|row| {
+                let name = names
+                    .get(&row.index)
+                    .or_else(|| descriptions.get(&row.index))
+                    .cloned()
+                    .unwrap_or_else(|| {
+                        format!("Tray {}", row.index.last().copied().unwrap_or_default())
+                    });
+
+                let max_capacity = max_capacities.get(&row.index).copied().unwrap_or(-2);
+                // An unknown status (5) is assumed when the column is missing
+                let status =
+                    SubunitStatus::from_value(statuses.get(&row.index).copied().unwrap_or(5));
+
+                let mut tray = Tray::new(name, row.value, max_capacity, status);
+                tray.media_name = media_names.get(&row.index).cloned();
+                tray.media_size = match (
+                    dim_units.get(&row.index),
+                    feed_dims.get(&row.index),
+                    cross_feed_dims.get(&row.index),
+                ) {
+                    (Some(unit), Some(feed), Some(cross_feed)) => {
+                        tray::media_size(*unit, *feed, *cross_feed)
+                    }
+                    _ => None,
+                };
+
+                tray
+            })
+            .collect())
+    }
+
+    /// Walks the `prtOutputTable` into the output bins of the device.
+    ///
+    /// The remaining capacity column drives the row order. Bins without a name are named
+    /// after their description, or `Output N` from their row index.
+    pub async fn fetch_outputs(&self, client: &SnmpClient) -> Result<Vec<OutputBin>, AppError> {
+        let column = |column| self.walk_numeric_column(client, PRT_OUTPUT_ENTRY, column);
+        let text = |column| self.walk_string_column(client, PRT_OUTPUT_ENTRY, column);
+
+        let remaining = client
+            .walk::<i64>(&Self::column_oid(
+                PRT_OUTPUT_ENTRY,
+                COL_OUTPUT_REMAINING_CAPACITY,
+            ))
+            .await?;
+
+        if remaining.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let max_capacities = column(COL_OUTPUT_MAX_CAPACITY).await?;
+        let statuses = column(COL_OUTPUT_STATUS).await?;
+        let names = text(COL_OUTPUT_NAME).await?;
+        let descriptions = text(COL_OUTPUT_DESCRIPTION).await?;
+
+        Ok(remaining
+            .into_iter()
+            .map(|row| {
+                let name = names
+                    .get(&row.index)
+                    .or_else(|| descriptions.get(&row.index))
+                    .cloned()
+                    .unwrap_or_else(|| {
+                        format!("Output {}", row.index.last().copied().unwrap_or_default())
+                    });
+
+                let max_capacity = max_capacities.get(&row.index).copied().unwrap_or(-2);
+
+                let mut bin = OutputBin::new(name, row.value, max_capacity);
+                bin.status = statuses
+                    .get(&row.index)
+                    .copied()
+                    .map(SubunitStatus::from_value);
+
+                bin
+            })
+            .collect())
+    }
+
+    /// Reads the front-panel display text (`prtConsoleDisplayBufferText`).
+    ///
+    /// Non-blank lines are joined with ` / `. Returns [`None`] when the display is blank or
+    /// not exposed.
+    pub async fn fetch_console_message(
+        &self,
+        client: &SnmpClient,
+    ) -> Result<Option<String>, AppError> {
+        let lines = client
+            .walk::<String>(PRT_CONSOLE_DISPLAY_BUFFER_TEXT)
+            .await?;
+
+        Ok(console_message(lines.into_iter().map(|row| row.value)))
+    }
+
+    /// Fetches the serial number (`prtGeneralSerialNumber`) and lifetime impression counter
+    /// (`prtMarkerLifeCount`) in a single request, as enabled by the flags.
+    async fn fetch_extras(
+        &self,
+        client: &SnmpClient,
+    ) -> Result<(Option<String>, Option<Metrics>), AppError> {
+        let params = client.params();
+
+        let mut oids = Vec::new();
+        if params.extra_supplies {
+            oids.push(PRT_GENERAL_SERIAL_NUMBER.to_vec());
+        }
+        if params.metrics {
+            oids.push(PRT_MARKER_LIFE_COUNT.to_vec());
+        }
+
+        let values = client.get_many(&oids).await?;
+
+        let serial_number = values
+            .get::<String>(PRT_GENERAL_SERIAL_NUMBER)
+            .ok()
+            .filter(|s| !s.trim().is_empty());
+
+        let metrics = params.metrics.then(|| Metrics {
+            total_impressions: values.get::<i64>(PRT_MARKER_LIFE_COUNT).ok(),
+            ..Default::default()
+        });
+
+        Ok((serial_number, metrics))
+    }
+}
+
+#[async_trait]
+impl PrinterDriver for PrinterMibDriver {
+    fn is_compatible(&self, _printer_name: &str) -> bool {
+        true
+    }
+
+    async fn get_supplies(
+        &self,
+        client: &SnmpClient,
+        printer_name: &str,
+    ) -> Result<Printer, AppError> {
+        let params = client.params();
+        let rows = self.fetch_supply_rows(client).await?;
+
+        if rows.is_empty() {
+            return Err(AppError::new(ErrorKind::UnsupportedPrinter(
+                printer_name.to_string(),
+            )));
+        }
+
+        let (serial_number, metrics) = self.fetch_extras(client).await?;
+
+        Ok(build_printer(
+            printer_name,
+            serial_number,
+            &rows,
+            metrics,
+            params.extra_supplies,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::printer::supply::SupplyState;
+
+    fn row(description: &str, supply_type: SupplyType, max_capacity: i64, level: i64) -> SupplyRow {
+        SupplyRow {
+            description: description.to_string(),
+            supply_type,
+            class: SupplyClass::Consumed,
+            colorant: None,
+            max_capacity,
+            level,
+        }
+    }
+
+    #[test]
+    fn test_color_from_description() {
+        assert_eq!(
+            color_from_description("Black Toner Cartridge HP CF410A"),
+            Some("Black")
+        );
+        assert_eq!(color_from_description("TONER CYAN"), Some("Cyan"));
+        assert_eq!(color_from_description("Magenta Cartridge"), Some("Magenta"));
+        assert_eq!(color_from_description("yellow toner"), Some("Yellow"));
+        assert_eq!(color_from_description("Fuser Kit"), None);
+    }
+
+    #[test]
+    fn test_build_printer_color_device() {
+        let rows = [
+            row("Black Toner", SupplyType::Toner, 100, 80),
+            row("Cyan Toner", SupplyType::Toner, 100, 33),
+            row("Magenta Toner", SupplyType::TonerCartridge, 100, 66),
+            row("Yellow Toner", SupplyType::Toner, 200, 20),
+            row("Black Drum", SupplyType::Opc, 100, 40),
+            row("Fuser", SupplyType::Fuser, 100, 90),
+            row("Waste Toner Box", SupplyType::WasteToner, 100, 10),
+        ];
+
+        let printer = build_printer("Test", None, &rows, None, true);
+
+        assert_eq!(
+            printer.toners().black_toner.unwrap().level_percent,
+            Some(80)
+        );
+        assert_eq!(printer.toners().cyan_toner.unwrap().level_percent, Some(33));
+        assert_eq!(
+            printer.toners().magenta_toner.unwrap().level_percent,
+            Some(66)
+        );
+        assert_eq!(
+            printer.toners().yellow_toner.unwrap().level_percent,
+            Some(10)
+        );
+        assert_eq!(printer.drums().black_drum.unwrap().level_percent, Some(40));
+        assert_eq!(printer.fuser().unwrap().level_percent, Some(90));
+        assert_eq!(printer.reservoir().unwrap().level_percent, Some(10));
+    }
+
+    #[test]
+    fn test_build_printer_mono_device_without_color_names() {
+        let rows = [
+            row("Toner Cartridge", SupplyType::Toner, 100, 55),
+            row("Imaging Unit", SupplyType::Opc, 100, 70),
+        ];
+
+        let printer = build_printer("Mono", None, &rows, None, true);
+
+        assert_eq!(
+            printer.toners().black_toner.unwrap().level_percent,
+            Some(55)
+        );
+        assert!(printer.toners().cyan_toner.is_none());
+        assert_eq!(printer.drums().black_drum.unwrap().level_percent, Some(70));
+    }
+
+    #[test]
+    fn test_build_printer_receptacle_class_is_reservoir() {
+        let mut waste = row("Collection Unit", SupplyType::Other, 100, 25);
+        waste.class = SupplyClass::Receptacle;
+
+        let printer = build_printer("Test", None, &[waste], None, true);
+
+        assert_eq!(printer.reservoir().unwrap().level_percent, Some(25));
+    }
+
+    #[test]
+    fn test_build_printer_keeps_other_supply_types() {
+        let rows = [
+            row("Transfer Belt", SupplyType::TransferUnit, 100, 60),
+            row("Staple Cartridge", SupplyType::Staples, -3, 5000),
+        ];
+
+        let printer = build_printer("Test", None, &rows, None, true);
+
+        let labels: Vec<String> = printer.supplies.iter().map(|s| s.label()).collect();
+        assert_eq!(labels, vec!["Transfer Unit", "Staples"]);
+        assert_eq!(printer.supplies[0].kind, PrinterSupply::Other);
+        assert_eq!(printer.supplies[0].level_percent, Some(60));
+        assert!(
+            build_printer("Test", None, &rows, None, false)
+                .supplies
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_build_printer_skips_extras_when_disabled() {
+        let rows = [
+            row("Black Toner", SupplyType::Toner, 100, 80),
+            row("Black Drum", SupplyType::Opc, 100, 40),
+            row("Fuser", SupplyType::Fuser, 100, 90),
+        ];
+
+        let printer = build_printer("Test", None, &rows, None, false);
+
+        assert!(printer.toners().black_toner.is_some());
+        assert!(printer.drums().black_drum.is_none());
+        assert!(printer.fuser().is_none());
+    }
+
+    #[test]
+    fn test_build_printer_special_levels() {
+        let rows = [
+            row("Black Toner", SupplyType::Toner, 100, -3),
+            row("Cyan Toner", SupplyType::Toner, -2, 50),
+            row("Magenta Toner", SupplyType::Toner, -1, 50),
+        ];
+
+        let printer = build_printer("Test", None, &rows, None, false);
+
+        let black = printer.toners().black_toner.unwrap();
+        assert_eq!(black.state, SupplyState::SomeRemaining);
+        assert_eq!(black.level_percent, None);
+        assert_eq!(
+            printer.toners().cyan_toner.unwrap().state,
+            SupplyState::Unknown
+        );
+        assert_eq!(
+            printer.toners().magenta_toner.unwrap().state,
+            SupplyState::Unlimited
+        );
+    }
+
+    #[test]
+    fn test_color_from_description_prefers_specific_names() {
+        assert_eq!(color_from_description("Light Cyan Ink"), Some("Light Cyan"));
+        assert_eq!(
+            color_from_description("Photo Black (PK)"),
+            Some("Photo Black")
+        );
+        assert_eq!(color_from_description("Grey Ink"), Some("Gray"));
+    }
+
+    #[test]
+    fn test_build_printer_keeps_every_supply() {
+        let rows = [
+            row("Cyan Ink", SupplyType::InkCartridge, 100, 70),
+            row("Light Cyan Ink", SupplyType::InkCartridge, 100, 40),
+            row("White Toner", SupplyType::Toner, 100, 90),
+        ];
+
+        let printer = build_printer("Photo", None, &rows, None, false);
+
+        let labels: Vec<String> = printer.supplies.iter().map(|s| s.label()).collect();
+        assert_eq!(labels, vec!["Cyan Ink", "Light Cyan Ink", "White Toner"]);
+        assert_eq!(printer.supplies[1].level_percent, Some(40));
+        assert_eq!(
+            printer.supplies[1].description.as_deref(),
+            Some("Light Cyan Ink")
+        );
+
+        // Only the standard colour appears in the legacy slots
+        assert_eq!(printer.toners().cyan_toner.unwrap().level_percent, Some(70));
+        assert!(printer.toners().black_toner.is_none());
+    }
+
+    #[test]
+    fn test_color_from_colorant() {
+        assert_eq!(color_from_colorant("cyan").as_deref(), Some("Cyan"));
+        assert_eq!(
+            color_from_colorant("lightCyan").as_deref(),
+            Some("Light Cyan")
+        );
+        assert_eq!(
+            color_from_colorant("PHOTO-BLACK").as_deref(),
+            Some("Photo Black")
+        );
+        assert_eq!(color_from_colorant("gold").as_deref(), Some("Gold"));
+        assert_eq!(color_from_colorant("unknown"), None);
+        assert_eq!(color_from_colorant(" "), None);
+    }
+
+    #[test]
+    fn test_colorant_key_uses_supply_device() {
+        assert_eq!(colorant_key(&[1, 4], 2), Some(vec![1, 2]));
+        assert_eq!(colorant_key(&[1, 4], 0), None);
+        assert_eq!(colorant_key(&[], 2), None);
+    }
+
+    #[test]
+    fn test_build_printer_prefers_colorant_over_description() {
+        let mut cartridge = row("Cartridge 1", SupplyType::InkCartridge, 100, 30);
+        cartridge.colorant = Some("lightMagenta".to_string());
+        let mut black = row("Cyan-compatible slot", SupplyType::Toner, 100, 60);
+        black.colorant = Some("black".to_string());
+
+        let printer = build_printer("Photo", None, &[cartridge, black], None, false);
+
+        assert_eq!(printer.supplies[0].label(), "Light Magenta Ink");
+        assert_eq!(
+            printer.supplies[0].colorant.as_deref(),
+            Some("lightMagenta")
+        );
+        assert_eq!(printer.supplies[1].label(), "Black Toner");
+        assert!(printer.toners().cyan_toner.is_none());
+    }
+
+    #[test]
+    fn test_console_message_joins_non_blank_lines() {
+        let lines = ["Load Tray 2   ", "A4\0\0", "   "].map(String::from);
+
+        assert_eq!(console_message(lines).as_deref(), Some("Load Tray 2 / A4"));
+        assert_eq!(console_message([String::new()]), None);
+    }
+}
@@ -10,6 +10,11 @@ impl AppError {
     pub fn new(kind: ErrorKind) -> Self {
         Self { kind }
     }
+
+    /// Returns the category of this error.
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
 }
 
 #[derive(Debug)]
@@ -22,6 +27,7 @@ pub enum ErrorKind {
     Parse(String),
     SnmpRequest(String),
     OidNotFound,
+    ResponseTooBig,
     InvalidDirectory,
     DirectoryRead,
     InvalidOidFormat,
@@ -41,6 +47,7 @@ impl fmt::Display for AppError {
             ErrorKind::Parse(s) => write!(f, "Failed to parse input: {s}"),
             ErrorKind::SnmpRequest(s) => write!(f, "SNMP communication failed: {s}"),
             ErrorKind::OidNotFound => write!(f, "Requested OID not found on device"),
+            ErrorKind::ResponseTooBig => write!(f, "Response too large for a single SNMP message"),
             ErrorKind::InvalidDirectory => write!(f, "Invalid or inaccessible directory path"),
             ErrorKind::DirectoryRead => write!(f, "Unable to read directory contents"),
             ErrorKind::InvalidOidFormat => {
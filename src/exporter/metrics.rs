@@ -0,0 +1,346 @@
+use crate::{fleet::FleetRecord, printer::supply::PrinterSupply};
+use std::fmt::Write;
+
+/// Escapes a label value as required by the Prometheus text exposition format.
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// The samples of one metric, rendered together under a single `HELP`/`TYPE` header.
+struct Family {
+    name: &'static str,
+    help: &'static str,
+    kind: &'static str,
+    samples: Vec<String>,
+}
+
+impl Family {
+    fn new(name: &'static str, help: &'static str, kind: &'static str) -> Self {
+        Self {
+            name,
+            help,
+            kind,
+            samples: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, labels: &[(&str, &str)], value: i64) {
+        let labels = labels
+            .iter()
+            .map(|(key, value)| format!("{key}=\"{}\"", escape_label(value)))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        self.samples
+            .push(format!("{}{{{labels}}} {value}", self.name));
+    }
+
+    fn render(&self, out: &mut String) {
+        if self.samples.is_empty() {
+            return;
+        }
+
+        let _ = writeln!(out, "# HELP {} {}", self.name, self.help);
+        let _ = writeln!(out, "# TYPE {} {}", self.name, self.kind);
+        for sample in &self.samples {
+            let _ = writeln!(out, "{sample}");
+        }
+    }
+}
+
+/// Renders the query results in the Prometheus text exposition format.
+pub fn render(records: &[FleetRecord]) -> String {
+    let mut up = Family::new(
+        "inkcheck_up",
+        "Whether the printer answered the last query (1) or not (0).",
+        "gauge",
+    );
+    let mut info = Family::new(
+        "inkcheck_printer_info",
+        "Printer model and serial number.",
+        "gauge",
+    );
+    let mut status = Family::new(
+        "inkcheck_printer_status",
+        "Printer status (hrPrinterStatus) and device status (hrDeviceStatus).",
+        "gauge",
+    );
+    let mut errors = Family::new(
+        "inkcheck_printer_error",
+        "Error conditions detected by the printer (hrPrinterDetectedErrorState).",
+        "gauge",
+    );
+    let mut level_percent = Family::new(
+        "inkcheck_supply_level_percent",
+        "Remaining supply life in percent.",
+        "gauge",
+    );
+    let mut level = Family::new(
+        "inkcheck_supply_level",
+        "Current supply level as reported by the printer.",
+        "gauge",
+    );
+    let mut max_level = Family::new(
+        "inkcheck_supply_max_level",
+        "Maximum supply capacity as reported by the printer.",
+        "gauge",
+    );
+    let mut state = Family::new(
+        "inkcheck_supply_state",
+        "Reported supply state (exact, some_remaining, unknown or unlimited).",
+        "gauge",
+    );
+    let mut impressions = Family::new(
+        "inkcheck_impressions_total",
+        "Impressions printed over the lifetime of the printer.",
+        "counter",
+    );
+
+    for record in records {
+        let target = [
+            ("alias", record.alias.as_str()),
+            ("host", record.host.as_str()),
+        ];
+
+        up.push(&target, i64::from(record.printer.is_some()));
+
+        let Some(printer) = &record.printer else {
+            continue;
+        };
+
+        info.push(
+            &[
+                target[0],
+                target[1],
+                ("model", printer.name.as_str()),
+                (
+                    "serial",
+                    printer.serial_number.as_deref().unwrap_or_default(),
+                ),
+            ],
+            1,
+        );
+
+        if printer.status.is_some() || printer.device_status.is_some() {
+            status.push(
+                &[
+                    target[0],
+                    target[1],
+                    (
+                        "status",
+                        printer.status.map(|s| s.name()).unwrap_or_default(),
+                    ),
+                    (
+                        "device_status",
+                        printer.device_status.map(|s| s.name()).unwrap_or_default(),
+                    ),
+                ],
+                1,
+            );
+        }
+
+        for error in &printer.errors {
+            errors.push(&[target[0], target[1], ("error", error.name())], 1);
+        }
+
+        for (position, reading) in printer.supplies.iter().enumerate() {
+            // Other supplies are told apart by their Printer-MIB type, e.g. `transfer_unit`
+            let supply = match reading.kind {
+                PrinterSupply::Other => reading.supply_type.name().to_string(),
+                PrinterSupply::Toner if reading.is_ink() => "ink".to_string(),
+                kind => kind.to_string().to_lowercase(),
+            };
+            let color = reading
+                .color
+                .as_deref()
+                .map(str::to_lowercase)
+                .unwrap_or_default();
+            // Two supplies may share a type and colour (e.g. two staple cartridges), so
+            // the position of the supply keeps each series unique
+            let index = (position + 1).to_string();
+            let labels = [
+                target[0],
+                target[1],
+                ("index", index.as_str()),
+                ("supply", supply.as_str()),
+                ("color", color.as_str()),
+            ];
+
+            if let Some(percent) = reading.level_percent {
+                level_percent.push(&labels, percent);
+            }
+            level.push(&labels, reading.level);
+            max_level.push(&labels, reading.max_level);
+            state.push(
+                &[
+                    labels[0],
+                    labels[1],
+                    labels[2],
+                    labels[3],
+                    labels[4],
+                    ("state", reading.state.name()),
+                ],
+                1,
+            );
+        }
+
+        if let Some(metrics) = &printer.metrics {
+            for (kind, count) in [
+                ("total", metrics.total_impressions),
+                ("mono", metrics.mono_impressions),
+                ("color", metrics.color_impressions),
+            ] {
+                if let Some(count) = count {
+                    impressions.push(&[target[0], target[1], ("kind", kind)], count);
+                }
+            }
+        }
+    }
+
+    let mut out = String::new();
+    for family in [
+        &up,
+        &info,
+        &status,
+        &errors,
+        &level_percent,
+        &level,
+        &max_level,
+        &state,
+        &impressions,
+    ] {
+        family.render(&mut out);
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::printer::{
+        Metrics, Printer,
+        status::{DeviceStatus, PrinterError, PrinterStatus},
+        supply::{
+            Supply,
+            drum::Drums,
+            marker::{SupplyClass, SupplyType},
+            toner::{Toner, Toners},
+        },
+    };
+
+    fn record(printer: Option<Printer>) -> FleetRecord {
+        FleetRecord {
+            alias: "office".to_string(),
+            host: "10.0.0.5".to_string(),
+            error: printer.is_none().then(|| "timeout".to_string()),
+            printer,
+        }
+    }
+
+    fn printer() -> Printer {
+        let mut printer = Printer::new(
+            "Brother HL-L8360CDW".to_string(),
+            Some("E123".to_string()),
+            Toners {
+                black_toner: Some(Toner::new(40, 100, None)),
+                ..Default::default()
+            },
+            Drums::default(),
+            None,
+            None,
+            Some(Metrics {
+                total_impressions: Some(1234),
+                ..Default::default()
+            }),
+        );
+        printer.calculate_all_levels();
+        printer.status = Some(PrinterStatus::Idle);
+        printer.device_status = Some(DeviceStatus::Warning);
+        printer.errors = vec![PrinterError::LowToner];
+        printer
+    }
+
+    #[test]
+    fn renders_supplies_and_counters() {
+        let out = render(&[record(Some(printer()))]);
+
+        assert!(out.contains("inkcheck_up{alias=\"office\",host=\"10.0.0.5\"} 1"));
+        assert!(out.contains(
+            "inkcheck_supply_level_percent{alias=\"office\",host=\"10.0.0.5\",index=\"1\",supply=\"toner\",color=\"black\"} 40"
+        ));
+        assert!(out.contains(
+            "inkcheck_supply_max_level{alias=\"office\",host=\"10.0.0.5\",index=\"1\",supply=\"toner\",color=\"black\"} 100"
+        ));
+        assert!(out.contains(
+            "inkcheck_impressions_total{alias=\"office\",host=\"10.0.0.5\",kind=\"total\"} 1234"
+        ));
+        assert!(out.contains(
+            "inkcheck_supply_state{alias=\"office\",host=\"10.0.0.5\",index=\"1\",supply=\"toner\",color=\"black\",state=\"exact\"} 1"
+        ));
+        assert!(out.contains(
+            "inkcheck_printer_status{alias=\"office\",host=\"10.0.0.5\",status=\"idle\",device_status=\"warning\"} 1"
+        ));
+        assert!(out.contains(
+            "inkcheck_printer_error{alias=\"office\",host=\"10.0.0.5\",error=\"low_toner\"} 1"
+        ));
+        assert!(out.contains("# TYPE inkcheck_impressions_total counter"));
+        assert!(!out.contains("kind=\"mono\""));
+    }
+
+    #[test]
+    fn same_kind_and_color_supplies_get_distinct_series() {
+        let staples = || {
+            Supply::new(PrinterSupply::Other, 50, 100, None)
+                .with_type(SupplyType::Staples, SupplyClass::Consumed)
+        };
+        let mut printer = Printer::from_supplies(
+            "Finisher".to_string(),
+            None,
+            vec![staples(), staples()],
+            None,
+        );
+        printer.calculate_all_levels();
+
+        let out = render(&[record(Some(printer))]);
+        let levels: Vec<&str> = out
+            .lines()
+            .filter(|line| line.starts_with("inkcheck_supply_level{"))
+            .collect();
+
+        assert_eq!(levels.len(), 2);
+        assert_ne!(levels[0], levels[1]);
+        assert!(out.contains(
+            "inkcheck_supply_level{alias=\"office\",host=\"10.0.0.5\",index=\"2\",supply=\"staples\",color=\"\"} 50"
+        ));
+    }
+
+    #[test]
+    fn ink_is_labelled_as_ink() {
+        let ink = Supply::new(PrinterSupply::Toner, 30, 100, None)
+            .with_type(SupplyType::Ink, SupplyClass::Consumed)
+            .with_color("Light Cyan");
+        let mut printer = Printer::from_supplies("Photo".to_string(), None, vec![ink], None);
+        printer.calculate_all_levels();
+
+        let out = render(&[record(Some(printer))]);
+
+        assert!(out.contains("supply=\"ink\",color=\"light cyan\"} 30"));
+    }
+
+    #[test]
+    fn failed_printer_is_reported_down() {
+        let out = render(&[record(None)]);
+
+        assert!(out.contains("inkcheck_up{alias=\"office\",host=\"10.0.0.5\"} 0"));
+        assert!(!out.contains("inkcheck_supply_level"));
+    }
+
+    #[test]
+    fn escapes_label_values() {
+        assert_eq!(escape_label("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+    }
+}
@@ -0,0 +1,280 @@
+use crate::{
+    cli::args::Args,
+    config::Config,
+    error::{AppError, ErrorKind},
+    fleet::{self, FleetRecord},
+    printer::Printer,
+};
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+mod metrics;
+
+/// Largest request head accepted from a client.
+const MAX_REQUEST_SIZE: usize = 8 * 1024;
+
+/// How long a client may take to send its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long to wait before accepting again after a failed accept.
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(100);
+
+const CONTENT_TYPE_METRICS: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+const LANDING_PAGE: &str = "<html>
+<head><title>inkcheck exporter</title></head>
+<body>
+<h1>inkcheck exporter</h1>
+<p><a href=\"/metrics\">Metrics</a> for every printer in the inventory.</p>
+<p><a href=\"/probe?target=\">Probe</a> a single alias or host with <code>/probe?target=&lt;alias|host&gt;</code>.</p>
+</body>
+</html>
+";
+
+/// Shared state of the exporter: the base CLI arguments every query starts from.
+struct Exporter {
+    args: Args,
+    jobs: usize,
+}
+
+/// A response ready to be written to the client.
+struct Response {
+    status: &'static str,
+    content_type: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn new(status: &'static str, content_type: &'static str, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            content_type,
+            body: body.into(),
+        }
+    }
+
+    fn text(status: &'static str, body: impl Into<String>) -> Self {
+        Self::new(status, "text/plain; charset=utf-8", body)
+    }
+}
+
+/// Runs the Prometheus exporter until the process is stopped.
+///
+/// `/metrics` queries every printer in the inventory, while `/probe?target=<alias|host>`
+/// queries a single printer, in the style of the SNMP exporter. Supplies and impression
+/// counters are always collected.
+pub async fn serve(args: &Args, listen: SocketAddr, jobs: usize) -> Result<(), AppError> {
+    let mut args = args.clone();
+    args.extra_supplies = true;
+    args.metrics = true;
+
+    let listener = TcpListener::bind(listen)
+        .await
+        .map_err(|e| AppError::new(ErrorKind::Io(format!("Failed to bind {listen}: {e}"))))?;
+
+    println!("📡 Exporter listening on http://{listen}");
+
+    let exporter = Arc::new(Exporter { args, jobs });
+
+    loop {
+        // Errors such as running out of file descriptors are transient: wait a moment
+        // instead of stopping the exporter.
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(e) => {
+                eprintln!("Error: failed to accept connection: {e}");
+                tokio::time::sleep(ACCEPT_BACKOFF).await;
+                continue;
+            }
+        };
+        let exporter = Arc::clone(&exporter);
+
+        tokio::spawn(async move {
+            if let Err(e) = exporter.handle(stream).await {
+                eprintln!("Error: {e}");
+            }
+        });
+    }
+}
+
+impl Exporter {
+    /// Reads a single request from the connection and answers it.
+    async fn handle(&self, mut stream: TcpStream) -> Result<(), AppError> {
+        let response =
+            match tokio::time::timeout(READ_TIMEOUT, read_request_head(&mut stream)).await {
+                Ok(Ok(head)) => self.route(&head).await,
+                Ok(Err(e)) => Response::text("400 Bad Request", format!("{e}\n")),
+                Err(_) => Response::text("408 Request Timeout", "Request timed out\n"),
+            };
+
+        let head = format!(
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            response.status,
+            response.content_type,
+            response.body.len()
+        );
+
+        stream.write_all(head.as_bytes()).await?;
+        stream.write_all(response.body.as_bytes()).await?;
+        stream.shutdown().await?;
+
+        Ok(())
+    }
+
+    /// Dispatches a request to the matching endpoint.
+    async fn route(&self, head: &str) -> Response {
+        let mut request_line = head.lines().next().unwrap_or_default().split_whitespace();
+        let method = request_line.next().unwrap_or_default();
+        let target = request_line.next().unwrap_or_default();
+
+        if method != "GET" {
+            return Response::text("405 Method Not Allowed", "Only GET is supported\n");
+        }
+
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+        match path {
+            "/" => Response::new("200 OK", "text/html; charset=utf-8", LANDING_PAGE),
+            "/metrics" => self.metrics().await,
+            "/probe" => match query_param(query, "target") {
+                Some(target) if !target.is_empty() => self.probe(&target).await,
+                _ => Response::text("400 Bad Request", "Missing 'target' parameter\n"),
+            },
+            _ => Response::text("404 Not Found", "Not found\n"),
+        }
+    }
+
+    /// Queries every printer in the inventory.
+    async fn metrics(&self) -> Response {
+        let inventory = match Config::load() {
+            Ok(inventory) => inventory,
+            Err(e) => {
+                return Response::text(
+                    "500 Internal Server Error",
+                    format!("Failed to load config: {e}\n"),
+                );
+            }
+        };
+
+        let records = fleet::poll_fleet(&self.args, inventory.printers, self.jobs, false).await;
+
+        Response::new("200 OK", CONTENT_TYPE_METRICS, metrics::render(&records))
+    }
+
+    /// Queries a single printer, by inventory alias or by host.
+    async fn probe(&self, target: &str) -> Response {
+        let inventory = Config::load().unwrap_or_default();
+
+        let record = match inventory.find_by_alias(target) {
+            Some(config) => {
+                let result = fleet::poll_printer(self.args.clone(), config, false).await;
+                record(&config.alias, &config.host, result)
+            }
+            None => {
+                let result = fleet::poll_host(&self.args, target).await;
+                record(target, target, result)
+            }
+        };
+
+        Response::new("200 OK", CONTENT_TYPE_METRICS, metrics::render(&[record]))
+    }
+}
+
+/// Wraps the result of a probe into the record rendered by [`metrics::render`].
+fn record(alias: &str, host: &str, result: Result<Printer, AppError>) -> FleetRecord {
+    FleetRecord {
+        alias: alias.to_string(),
+        host: host.to_string(),
+        error: result.as_ref().err().map(|e| e.to_string()),
+        printer: result.ok(),
+    }
+}
+
+/// Reads the request line and headers, up to the blank line that ends them.
+async fn read_request_head(stream: &mut TcpStream) -> Result<String, AppError> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 1024];
+
+    while !buffer.windows(4).any(|w| w == b"\r\n\r\n") {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            break;
+        }
+
+        buffer.extend_from_slice(&chunk[..read]);
+
+        if buffer.len() > MAX_REQUEST_SIZE {
+            return Err(AppError::new(ErrorKind::Parse(
+                "Request too large".to_string(),
+            )));
+        }
+    }
+
+    String::from_utf8(buffer)
+        .map_err(|_| AppError::new(ErrorKind::Parse("Request is not valid UTF-8".to_string())))
+}
+
+/// Returns the decoded value of a query string parameter.
+fn query_param(query: &str, name: &str) -> Option<String> {
+    query
+        .split('&')
+        .map(|pair| pair.split_once('=').unwrap_or((pair, "")))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| percent_decode(value))
+}
+
+/// Decodes `%XX` escapes and `+` in a query string value.
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let byte = std::str::from_utf8(&bytes[i + 1..i + 3])
+                    .ok()
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+                match byte {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_query_parameter() {
+        assert_eq!(
+            query_param("module=x&target=office", "target").as_deref(),
+            Some("office")
+        );
+        assert_eq!(query_param("target", "target").as_deref(), Some(""));
+        assert_eq!(query_param("module=x", "target"), None);
+    }
+
+    #[test]
+    fn decodes_percent_escapes() {
+        assert_eq!(percent_decode("hr%2Dprinter"), "hr-printer");
+        assert_eq!(percent_decode("a+b"), "a b");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz"), "%zz");
+    }
+}
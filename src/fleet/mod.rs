@@ -0,0 +1,118 @@
+use crate::{
+    cli::{self, args::Args},
+    config::{self, Config, PrinterConfig},
+    error::{AppError, ErrorKind},
+    history,
+    printer::Printer,
+    snmp::{self, SnmpClientParams, client::SnmpClient},
+};
+use serde::Serialize;
+use std::sync::Arc;
+use tokio::{sync::Semaphore, task::JoinSet};
+
+/// The outcome of polling a single inventory printer.
+///
+/// Exactly one of `printer` or `error` is set, so a failing device never hides the others.
+#[derive(Serialize, Debug)]
+pub struct FleetRecord {
+    pub alias: String,
+    pub host: String,
+    pub printer: Option<Printer>,
+    pub error: Option<String>,
+}
+
+/// Selects the inventory printers to poll.
+///
+/// An empty `aliases` list selects every configured printer. Unknown aliases are reported
+/// as an error rather than silently skipped.
+pub fn select_printers(
+    inventory: &Config,
+    aliases: &[String],
+) -> Result<Vec<PrinterConfig>, AppError> {
+    if inventory.printers.is_empty() {
+        return Err(AppError::new(ErrorKind::Cli(
+            "No printers configured. Run 'inkcheck --init' and add printers to the inventory."
+                .to_string(),
+        )));
+    }
+
+    if aliases.is_empty() {
+        return Ok(inventory.printers.clone());
+    }
+
+    aliases
+        .iter()
+        .map(|alias| {
+            inventory.find_by_alias(alias).cloned().ok_or_else(|| {
+                AppError::new(ErrorKind::Cli(format!(
+                    "No printer with alias '{alias}' in the inventory."
+                )))
+            })
+        })
+        .collect()
+}
+
+/// Resolves a printer's settings on top of the base CLI arguments and queries it.
+///
+/// When `record` is set, the snapshot is recorded in the history store if recording is
+/// enabled for the printer. The exporter polls far too often to record every scrape.
+pub async fn poll_printer(
+    mut args: Args,
+    config: &PrinterConfig,
+    record: bool,
+) -> Result<Printer, AppError> {
+    config::apply_config_to_args(&mut args, config);
+
+    let printer = poll_host(&args, &config.host).await?;
+
+    if record && args.record {
+        history::record(&config.alias, &config.host, &printer).await;
+    }
+
+    Ok(printer)
+}
+
+/// Queries a single host using the connection settings in `args`.
+pub async fn poll_host(args: &Args, host: &str) -> Result<Printer, AppError> {
+    let ip = cli::resolve_host(host, args.port)?;
+    let client = SnmpClient::new(SnmpClientParams::from_args(args, ip)).await?;
+
+    snmp::get_printer_values(&client).await
+}
+
+/// Polls every selected printer concurrently, with at most `jobs` queries in flight.
+///
+/// Records are returned in the same order as `printers`. See [`poll_printer`] for `record`.
+pub async fn poll_fleet(
+    args: &Args,
+    printers: Vec<PrinterConfig>,
+    jobs: usize,
+    record: bool,
+) -> Vec<FleetRecord> {
+    let permits = Arc::new(Semaphore::new(jobs.max(1)));
+    let mut tasks = JoinSet::new();
+
+    for (position, config) in printers.into_iter().enumerate() {
+        let args = args.clone();
+        let permits = Arc::clone(&permits);
+
+        tasks.spawn(async move {
+            let _permit = permits.acquire_owned().await;
+            let result = poll_printer(args, &config, record).await;
+
+            let record = FleetRecord {
+                alias: config.alias,
+                host: config.host,
+                error: result.as_ref().err().map(|e| e.to_string()),
+                printer: result.ok(),
+            };
+
+            (position, record)
+        });
+    }
+
+    let mut records: Vec<(usize, FleetRecord)> = tasks.join_all().await;
+    records.sort_by_key(|(position, _)| *position);
+
+    records.into_iter().map(|(_, record)| record).collect()
+}
@@ -0,0 +1,183 @@
+use super::HistoryEntry;
+use chrono::{DateTime, Duration, FixedOffset};
+use serde::Serialize;
+
+/// Projected depletion of a single supply, based on its recorded consumption.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct SupplyForecast {
+    pub supply: String,
+    /// Percentage points used per day.
+    pub percent_per_day: Option<f64>,
+    /// Percentage points used per printed page.
+    pub percent_per_page: Option<f64>,
+    pub days_remaining: Option<f64>,
+    pub pages_remaining: Option<i64>,
+    /// Projected date the supply reaches 0%, as `YYYY-MM-DD`.
+    pub empty_on: Option<String>,
+}
+
+impl SupplyForecast {
+    /// Short human-readable summary, e.g. `~23 days, ~1200 pages left (2026-11-08)`.
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+
+        if let Some(days) = self.days_remaining {
+            parts.push(format!("~{days:.0} days"));
+        }
+        if let Some(pages) = self.pages_remaining {
+            parts.push(format!("~{pages} pages"));
+        }
+
+        let mut summary = format!("{} left", parts.join(", "));
+        if let Some(date) = &self.empty_on {
+            summary.push_str(&format!(" ({date})"));
+        }
+
+        summary
+    }
+}
+
+/// A reading of one supply at one point in time.
+struct Sample {
+    time: DateTime<FixedOffset>,
+    percent: i64,
+    pages: Option<i64>,
+}
+
+/// Forecasts every supply found in the latest snapshot.
+///
+/// Only readings taken since the supply was last replaced (its level went up) are used.
+/// Supplies that have not been consumed over that span get no forecast.
+pub fn forecast(entries: &[HistoryEntry]) -> Vec<SupplyForecast> {
+    let Some(last) = entries.last() else {
+        return Vec::new();
+    };
+
+    last.supplies
+        .iter()
+        .filter_map(|supply| forecast_supply(&supply.name, entries))
+        .collect()
+}
+
+fn forecast_supply(name: &str, entries: &[HistoryEntry]) -> Option<SupplyForecast> {
+    let samples: Vec<Sample> = entries
+        .iter()
+        .filter_map(|entry| {
+            Some(Sample {
+                time: entry.time()?,
+                percent: entry.percent(name)?,
+                pages: entry.total_impressions,
+            })
+        })
+        .collect();
+
+    // Start after the most recent refill
+    let start = samples
+        .windows(2)
+        .rposition(|w| w[1].percent > w[0].percent)
+        .map_or(0, |i| i + 1);
+
+    let first = samples.get(start)?;
+    let last = samples.last()?;
+
+    let used = (first.percent - last.percent) as f64;
+    if used <= 0.0 {
+        return None;
+    }
+
+    let days = (last.time - first.time).num_seconds() as f64 / 86_400.0;
+    let pages = match (first.pages, last.pages) {
+        (Some(start), Some(end)) if end > start => Some((end - start) as f64),
+        _ => None,
+    };
+
+    let remaining = last.percent as f64;
+
+    let percent_per_day = (days > 0.0).then(|| used / days);
+    let days_remaining = (days > 0.0).then(|| remaining * days / used);
+
+    let percent_per_page = pages.map(|pages| used / pages);
+    let pages_remaining = pages.map(|pages| (remaining * pages / used).round() as i64);
+
+    if days_remaining.is_none() && pages_remaining.is_none() {
+        return None;
+    }
+
+    let empty_on = days_remaining.map(|days| {
+        let seconds = (days * 86_400.0) as i64;
+        (last.time + Duration::seconds(seconds))
+            .format("%Y-%m-%d")
+            .to_string()
+    });
+
+    Some(SupplyForecast {
+        supply: name.to_string(),
+        percent_per_day,
+        percent_per_page,
+        days_remaining,
+        pages_remaining,
+        empty_on,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::SupplyRecord;
+
+    fn entry(timestamp: &str, black: i64, pages: i64) -> HistoryEntry {
+        HistoryEntry {
+            timestamp: timestamp.to_string(),
+            alias: "office".to_string(),
+            host: "10.0.0.5".to_string(),
+            model: "Test Printer".to_string(),
+            serial_number: None,
+            supplies: vec![SupplyRecord {
+                name: "Black Toner".to_string(),
+                level: black,
+                max_level: 100,
+                level_percent: Some(black),
+            }],
+            total_impressions: Some(pages),
+            mono_impressions: None,
+            color_impressions: None,
+        }
+    }
+
+    #[test]
+    fn projects_days_and_pages() {
+        let forecasts = forecast(&[
+            entry("2025-01-01T00:00:00+00:00", 80, 1000),
+            entry("2025-01-11T00:00:00+00:00", 60, 2000),
+        ]);
+
+        let black = &forecasts[0];
+        assert_eq!(black.percent_per_day, Some(2.0));
+        assert_eq!(black.percent_per_page, Some(0.02));
+        assert_eq!(black.days_remaining, Some(30.0));
+        assert_eq!(black.pages_remaining, Some(3000));
+        assert_eq!(black.empty_on.as_deref(), Some("2025-02-10"));
+    }
+
+    #[test]
+    fn ignores_readings_before_refill() {
+        let forecasts = forecast(&[
+            entry("2025-01-01T00:00:00+00:00", 10, 0),
+            entry("2025-01-02T00:00:00+00:00", 100, 100),
+            entry("2025-01-06T00:00:00+00:00", 90, 600),
+        ]);
+
+        assert_eq!(forecasts[0].percent_per_day, Some(2.5));
+        assert_eq!(forecasts[0].pages_remaining, Some(4500));
+    }
+
+    #[test]
+    fn unused_supply_has_no_forecast() {
+        let forecasts = forecast(&[
+            entry("2025-01-01T00:00:00+00:00", 80, 1000),
+            entry("2025-01-02T00:00:00+00:00", 80, 1000),
+        ]);
+
+        assert!(forecasts.is_empty());
+    }
+}
@@ -0,0 +1,428 @@
+use crate::{
+    error::{AppError, ErrorKind},
+    printer::Printer,
+};
+use chrono::{DateTime, FixedOffset, Local};
+use forecast::forecast;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    sync::Mutex,
+};
+
+pub mod forecast;
+
+/// Serializes appends from concurrent fleet tasks, so records never interleave.
+static APPEND_LOCK: Mutex<()> = Mutex::new(());
+
+/// Size above which the history file is compacted on the next append.
+const MAX_FILE_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Readings kept per printer when the history file is compacted.
+const MAX_ENTRIES_PER_ALIAS: usize = 1_000;
+
+/// A supply level as stored in the history file.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SupplyRecord {
+    pub name: String,
+    pub level: i64,
+    pub max_level: i64,
+    pub level_percent: Option<i64>,
+}
+
+/// A single printer snapshot, stored as one JSON line.
+///
+/// Supplies are flattened into named records so that old entries stay readable when
+/// the [`Printer`] model changes.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HistoryEntry {
+    pub timestamp: String,
+    pub alias: String,
+    pub host: String,
+    pub model: String,
+    pub serial_number: Option<String>,
+    pub supplies: Vec<SupplyRecord>,
+    pub total_impressions: Option<i64>,
+    pub mono_impressions: Option<i64>,
+    pub color_impressions: Option<i64>,
+}
+
+impl HistoryEntry {
+    /// Captures the current state of a printer.
+    pub fn new(alias: &str, host: &str, printer: &Printer, timestamp: DateTime<Local>) -> Self {
+        let metrics = printer.metrics.unwrap_or_default();
+
+        Self {
+            timestamp: timestamp.to_rfc3339(),
+            alias: alias.to_string(),
+            host: host.to_string(),
+            model: printer.name.clone(),
+            serial_number: printer.serial_number.clone(),
+            supplies: printer
+                .supplies
+                .iter()
+                .map(|s| SupplyRecord {
+                    name: s.label(),
+                    level: s.level,
+                    max_level: s.max_level,
+                    level_percent: s.level_percent,
+                })
+                .collect(),
+            total_impressions: metrics.total_impressions,
+            mono_impressions: metrics.mono_impressions,
+            color_impressions: metrics.color_impressions,
+        }
+    }
+
+    fn time(&self) -> Option<DateTime<FixedOffset>> {
+        DateTime::parse_from_rfc3339(&self.timestamp).ok()
+    }
+
+    /// Level percentage of the named supply in this snapshot.
+    pub fn percent(&self, supply: &str) -> Option<i64> {
+        self.supplies
+            .iter()
+            .find(|s| s.name == supply)
+            .and_then(|s| s.level_percent)
+    }
+}
+
+/// How a supply level changed between the first and last recorded snapshots.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct SupplyTrend {
+    pub name: String,
+    pub first_percent: i64,
+    pub last_percent: i64,
+    pub change: i64,
+    /// Average change per day, when the readings span a measurable amount of time.
+    pub per_day: Option<f64>,
+}
+
+/// Computes per-supply trends over a series of snapshots, oldest first.
+pub fn trends(entries: &[HistoryEntry]) -> Vec<SupplyTrend> {
+    let Some(last) = entries.last() else {
+        return Vec::new();
+    };
+
+    last.supplies
+        .iter()
+        .filter_map(|supply| {
+            let (first, first_percent) = entries
+                .iter()
+                .find_map(|e| e.percent(&supply.name).map(|p| (e, p)))?;
+            let last_percent = supply.level_percent?;
+            let change = last_percent - first_percent;
+
+            let days = match (first.time(), last.time()) {
+                (Some(start), Some(end)) => (end - start).num_seconds() as f64 / 86_400.0,
+                _ => 0.0,
+            };
+
+            Some(SupplyTrend {
+                name: supply.name.clone(),
+                first_percent,
+                last_percent,
+                change,
+                per_day: (days > 0.0).then(|| change as f64 / days),
+            })
+        })
+        .collect()
+}
+
+/// An append-only JSON Lines file holding printer snapshots.
+pub struct HistoryStore {
+    path: PathBuf,
+}
+
+impl HistoryStore {
+    /// Opens the store at the given path.
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Opens the store in the user's data directory.
+    pub fn open_default() -> Self {
+        Self::new(get_history_path())
+    }
+
+    /// Appends a snapshot to the store, creating the file if needed.
+    pub fn append(&self, entry: &HistoryEntry) -> Result<(), AppError> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let json = serde_json::to_string(entry)
+            .map_err(|e| AppError::new(ErrorKind::Parse(e.to_string())))?;
+        let line = format!("{json}\n");
+
+        let _guard = APPEND_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+
+        // A single write, so the line and its newline are appended together
+        file.write_all(line.as_bytes())?;
+
+        if file.metadata()?.len() > MAX_FILE_BYTES {
+            self.compact()?;
+        }
+
+        Ok(())
+    }
+
+    /// Rewrites the file with only the most recent readings.
+    ///
+    /// Must be called with [`APPEND_LOCK`] held. The new content is written to a temporary
+    /// file first, so an interrupted compaction never loses the history.
+    fn compact(&self) -> Result<(), AppError> {
+        let content = fs::read_to_string(&self.path)?;
+        let compacted = compact_lines(&content, MAX_ENTRIES_PER_ALIAS, MAX_FILE_BYTES / 2);
+
+        let temp = self.path.with_extension("jsonl.tmp");
+        fs::write(&temp, compacted)?;
+        fs::rename(&temp, &self.path)?;
+
+        Ok(())
+    }
+
+    /// Loads every snapshot recorded for `alias`, oldest first.
+    ///
+    /// Lines that cannot be parsed are skipped, so one damaged entry does not hide the rest.
+    pub fn entries(&self, alias: &str) -> Result<Vec<HistoryEntry>, AppError> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&self.path)?;
+
+        Ok(content
+            .lines()
+            .filter_map(|line| serde_json::from_str::<HistoryEntry>(line).ok())
+            .filter(|entry| entry.alias == alias)
+            .collect())
+    }
+}
+
+/// Keeps the newest `per_alias` readings of each printer, then drops the oldest of those
+/// until the result fits in `max_bytes`. Lines that cannot be parsed are dropped.
+fn compact_lines(content: &str, per_alias: usize, max_bytes: u64) -> String {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    let mut size = 0;
+
+    let mut kept: Vec<&str> = content
+        .lines()
+        .rev()
+        .filter(|line| {
+            let Ok(entry) = serde_json::from_str::<HistoryEntry>(line) else {
+                return false;
+            };
+
+            let count = counts.entry(entry.alias).or_default();
+            *count += 1;
+            *count <= per_alias
+        })
+        .take_while(|line| {
+            size += line.len() as u64 + 1;
+            size <= max_bytes
+        })
+        .collect();
+
+    kept.reverse();
+    kept.iter().map(|line| format!("{line}\n")).collect()
+}
+
+/// Records a snapshot in the default store.
+///
+/// History is a side feature, so a failure is reported as a warning instead of failing
+/// the query that produced the snapshot. The file is written on the blocking pool.
+pub async fn record(alias: &str, host: &str, printer: &Printer) {
+    let entry = HistoryEntry::new(alias, host, printer, Local::now());
+
+    let result = tokio::task::spawn_blocking(move || HistoryStore::open_default().append(&entry))
+        .await
+        .map_err(|e| AppError::new(ErrorKind::Io(e.to_string())))
+        .and_then(|result| result);
+
+    if let Err(e) = result {
+        eprintln!("Warning: failed to record history for '{alias}': {e}");
+    }
+}
+
+/// Attaches depletion forecasts to a freshly queried printer.
+///
+/// The recorded readings for `alias` are combined with the current snapshot, unless it
+/// was just recorded. Printers without any recorded history get no forecast.
+pub async fn attach_forecasts(alias: &str, host: &str, printer: &mut Printer, recorded: bool) {
+    let owned_alias = alias.to_string();
+    let mut entries =
+        tokio::task::spawn_blocking(move || HistoryStore::open_default().entries(&owned_alias))
+            .await
+            .ok()
+            .and_then(Result::ok)
+            .unwrap_or_default();
+
+    if entries.is_empty() {
+        return;
+    }
+
+    if !recorded {
+        entries.push(HistoryEntry::new(alias, host, printer, Local::now()));
+    }
+
+    printer.forecasts = forecast(&entries);
+}
+
+/// Resolves the history file path based on the OS standard.
+fn get_history_path() -> PathBuf {
+    use directories::ProjectDirs;
+
+    // Windows: %APPDATA%\allansomensi\inkcheck\data\history.jsonl
+    // Linux: ~/.local/share/inkcheck/history.jsonl
+    // macOS: ~/Library/Application Support/com.allansomensi.inkcheck/history.jsonl
+    if let Some(proj_dirs) = ProjectDirs::from("com", "allansomensi", "inkcheck") {
+        return proj_dirs.data_dir().join("history.jsonl");
+    }
+
+    // Fallback to current directory
+    PathBuf::from("history.jsonl")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(timestamp: &str, alias: &str, black: i64) -> HistoryEntry {
+        HistoryEntry {
+            timestamp: timestamp.to_string(),
+            alias: alias.to_string(),
+            host: "10.0.0.5".to_string(),
+            model: "Test Printer".to_string(),
+            serial_number: None,
+            supplies: vec![SupplyRecord {
+                name: "Black Toner".to_string(),
+                level: black,
+                max_level: 100,
+                level_percent: Some(black),
+            }],
+            total_impressions: None,
+            mono_impressions: None,
+            color_impressions: None,
+        }
+    }
+
+    #[test]
+    fn computes_daily_trend() {
+        let entries = vec![
+            entry("2025-01-01T00:00:00+00:00", "office", 80),
+            entry("2025-01-11T00:00:00+00:00", "office", 60),
+        ];
+
+        let trends = trends(&entries);
+
+        assert_eq!(trends.len(), 1);
+        assert_eq!(trends[0].change, -20);
+        assert_eq!(trends[0].per_day, Some(-2.0));
+    }
+
+    #[test]
+    fn single_entry_has_no_rate() {
+        let trends = trends(&[entry("2025-01-01T00:00:00+00:00", "office", 80)]);
+
+        assert_eq!(trends[0].change, 0);
+        assert_eq!(trends[0].per_day, None);
+    }
+
+    #[test]
+    fn store_round_trip_filters_by_alias() {
+        let path = std::env::temp_dir().join(format!(
+            "inkcheck-history-test-{}.jsonl",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        let store = HistoryStore::new(path.clone());
+
+        store
+            .append(&entry("2025-01-01T00:00:00+00:00", "office", 80))
+            .unwrap();
+        store
+            .append(&entry("2025-01-02T00:00:00+00:00", "lab", 50))
+            .unwrap();
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"not json\n")
+            .unwrap();
+
+        let entries = store.entries("office").unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].percent("Black Toner"), Some(80));
+    }
+
+    #[test]
+    fn concurrent_appends_keep_every_line() {
+        let path = std::env::temp_dir().join(format!(
+            "inkcheck-history-concurrent-{}.jsonl",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    let store = HistoryStore::new(path.clone());
+                    for _ in 0..25 {
+                        store
+                            .append(&entry("2025-01-01T00:00:00+00:00", "office", 80))
+                            .unwrap();
+                    }
+                });
+            }
+        });
+
+        let entries = HistoryStore::new(path.clone()).entries("office").unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(entries.len(), 200);
+    }
+
+    #[test]
+    fn compaction_keeps_the_newest_readings() {
+        let lines: String = [
+            entry("2025-01-01T00:00:00+00:00", "office", 90),
+            entry("2025-01-02T00:00:00+00:00", "lab", 70),
+            entry("2025-01-03T00:00:00+00:00", "office", 80),
+            entry("2025-01-04T00:00:00+00:00", "office", 60),
+        ]
+        .iter()
+        .map(|e| format!("{}\n", serde_json::to_string(e).unwrap()))
+        .chain(["not json\n".to_string()])
+        .collect();
+
+        let compacted = compact_lines(&lines, 2, u64::MAX);
+        let levels: Vec<(String, Option<i64>)> = compacted
+            .lines()
+            .map(|line| serde_json::from_str::<HistoryEntry>(line).unwrap())
+            .map(|e| (e.alias.clone(), e.percent("Black Toner")))
+            .collect();
+
+        assert_eq!(
+            levels,
+            vec![
+                ("lab".to_string(), Some(70)),
+                ("office".to_string(), Some(80)),
+                ("office".to_string(), Some(60))
+            ]
+        );
+
+        let last_line = lines.lines().nth(3).unwrap().len() as u64 + 1;
+        assert_eq!(compact_lines(&lines, 2, last_line).lines().count(), 1);
+    }
+}
@@ -2,27 +2,58 @@ mod cli;
 mod config;
 mod driver;
 mod error;
+mod exporter;
+mod fleet;
+mod history;
 mod printer;
 mod snmp;
 mod utils;
 
 use clap::Parser;
-use cli::args::Args;
+use cli::{
+    args::Args,
+    nagios::{CheckResult, PluginStatus, Thresholds},
+    output::OutputFormat,
+};
 use error::{AppError, ErrorKind};
-use std::process;
+use std::{process, time::Duration};
 
 #[tokio::main]
 async fn main() {
-    if let Err(e) = run().await {
+    let args = Args::parse();
+    let nagios = matches!(args.output, OutputFormat::Nagios);
+
+    if let Err(e) = run(args).await {
+        if nagios {
+            println!("INKCHECK {} - {e}", PluginStatus::Unknown);
+            process::exit(PluginStatus::Unknown.exit_code());
+        }
+
         eprintln!("Error: {e}");
         process::exit(1);
     }
 }
 
+/// Prints a Nagios check result and exits with its plugin status code.
+fn exit_with_check(result: CheckResult) -> ! {
+    println!("{result}");
+    process::exit(result.status.exit_code());
+}
+
 /// Orchestrates the application flow: configuration loading, argument parsing,
 /// and execution of the main logic.
-async fn run() -> Result<(), AppError> {
-    let mut args = Args::parse();
+async fn run(mut args: Args) -> Result<(), AppError> {
+    // Thresholds only apply to the Nagios check, so other modes never reject them.
+    let thresholds = match args.output {
+        OutputFormat::Nagios => Some(Thresholds::new(args.warning, args.critical)?),
+        _ => None,
+    };
+
+    if args.watch.is_some() && matches!(args.output, OutputFormat::Nagios) {
+        return Err(AppError::new(ErrorKind::Cli(
+            "'--watch' cannot be combined with Nagios output.".to_string(),
+        )));
+    }
 
     if let Some(cmd) = &args.command {
         match cmd {
@@ -30,6 +61,57 @@ async fn run() -> Result<(), AppError> {
                 printer::scan::run_mdns_scan(*timeout).await;
                 return Ok(());
             }
+            cli::commands::Commands::Fleet { aliases, jobs } => {
+                let inventory = config::Config::load().map_err(|e| {
+                    AppError::new(ErrorKind::Io(format!("Failed to load config: {e}")))
+                })?;
+                let printers = fleet::select_printers(&inventory, aliases)?;
+                let records = fleet::poll_fleet(&args, printers, *jobs, true).await;
+
+                if let Some(thresholds) = &thresholds {
+                    exit_with_check(cli::nagios::check_fleet(&records, thresholds));
+                }
+
+                cli::display::show_fleet_values(&records, args.metrics, &args.output);
+                return Ok(());
+            }
+            cli::commands::Commands::History { alias, limit } => {
+                let mut entries = history::HistoryStore::open_default().entries(alias)?;
+
+                if let Some(limit) = limit {
+                    entries.drain(..entries.len().saturating_sub(*limit));
+                }
+
+                let trends = history::trends(&entries);
+                cli::display::show_history(alias, &entries, &trends, &args.output);
+                return Ok(());
+            }
+            cli::commands::Commands::Serve { listen, jobs } => {
+                return exporter::serve(&args, *listen, *jobs).await;
+            }
+            cli::commands::Commands::Data { command } => match command {
+                cli::commands::DataCommands::Validate { dir } => {
+                    let sources = match dir {
+                        Some(dir) => vec![printer::load::DataSource::Cli(dir.clone())],
+                        None => printer::load::DataSource::layers(args.data_dir.clone())
+                            .into_iter()
+                            .filter(printer::load::DataSource::exists)
+                            .collect(),
+                    };
+
+                    let problems = printer::validate::validate(&sources)?;
+                    cli::display::show_validation(&sources, &problems);
+
+                    if !problems.is_empty() {
+                        return Err(AppError::new(ErrorKind::Parse(format!(
+                            "{} problem(s) found in data files",
+                            problems.len()
+                        ))));
+                    }
+
+                    return Ok(());
+                }
+            },
         }
     }
 
@@ -43,13 +125,19 @@ async fn run() -> Result<(), AppError> {
 
     let inventory = config::Config::load().unwrap_or_default();
 
+    // Readings are recorded under the name the printer was requested by.
+    let alias = args.host.clone().unwrap_or_default();
+
     if let Some(host_input) = &args.host
         && let Some(saved_printer) = inventory.find_by_alias(host_input)
     {
-        println!(
-            "📂 Loading saved configuration for: '{}'",
-            saved_printer.alias
-        );
+        // Plugin output must be a single status line, so skip the banner.
+        if !matches!(args.output, OutputFormat::Nagios) {
+            println!(
+                "📂 Loading saved configuration for: '{}'",
+                saved_printer.alias
+            );
+        }
         config::apply_config_to_args(&mut args, saved_printer);
     }
 
@@ -69,12 +157,35 @@ async fn run() -> Result<(), AppError> {
         snmp: snmp::SnmpClientParams::from_args(&args, ip),
     };
 
-    let printer = snmp::get_printer_values(&params.snmp).await?;
+    let client = snmp::client::SnmpClient::new(params.snmp).await?;
+
+    if let Some(seconds) = args.watch {
+        return cli::watch::watch(&client, Duration::from_secs(seconds), &params.app).await;
+    }
+
+    let mut printer = snmp::get_printer_values(&client).await?;
+
+    if args.record {
+        history::record(&alias, host, &printer).await;
+    }
+
+    history::attach_forecasts(&alias, host, &mut printer, args.record).await;
+
+    if args.explain {
+        let sources = printer::load::DataSource::layers(client.params().data_dir.clone());
+        cli::display::show_data_explanation(&printer, &sources);
+    }
+
+    if let Some(thresholds) = &thresholds {
+        exit_with_check(cli::nagios::check_printer(&printer, thresholds));
+    }
 
     cli::display::show_printer_values(
         printer,
-        params.snmp.extra_supplies,
-        params.snmp.metrics,
+        client.params().extra_supplies,
+        client.params().metrics,
+        client.params().paper,
+        client.params().info,
         &params.app.theme,
         &params.app.output,
     );
@@ -0,0 +1,449 @@
+use serde::{Serialize, Serializer};
+use std::fmt::Display;
+
+/// How urgent an alert is (`prtAlertSeverityLevel`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertSeverity {
+    Other,
+    Critical,
+    Warning,
+    /// A warning that clears itself, e.g. a cover that was opened and closed again.
+    WarningBinaryChangeEvent,
+}
+
+impl AlertSeverity {
+    /// Decodes a `PrtAlertSeverityLevelTC` value.
+    pub fn from_value(value: i64) -> Self {
+        match value {
+            3 => Self::Critical,
+            4 => Self::Warning,
+            5 => Self::WarningBinaryChangeEvent,
+            _ => Self::Other,
+        }
+    }
+}
+
+impl Display for AlertSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Other => write!(f, "Info"),
+            Self::Critical => write!(f, "Critical"),
+            Self::Warning | Self::WarningBinaryChangeEvent => write!(f, "Warning"),
+        }
+    }
+}
+
+/// Who is expected to deal with an alert (`prtAlertTrainingLevel`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertTrainingLevel {
+    Other,
+    Unknown,
+    Untrained,
+    Trained,
+    FieldService,
+    Management,
+    NoInterventionRequired,
+}
+
+impl AlertTrainingLevel {
+    /// Decodes a `PrtAlertTrainingLevelTC` value.
+    pub fn from_value(value: i64) -> Self {
+        match value {
+            1 => Self::Other,
+            3 => Self::Untrained,
+            4 => Self::Trained,
+            5 => Self::FieldService,
+            6 => Self::Management,
+            7 => Self::NoInterventionRequired,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+impl Display for AlertTrainingLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Other => write!(f, "other"),
+            Self::Unknown => write!(f, "unknown"),
+            Self::Untrained => write!(f, "anyone"),
+            Self::Trained => write!(f, "trained user"),
+            Self::FieldService => write!(f, "field service"),
+            Self::Management => write!(f, "management"),
+            Self::NoInterventionRequired => write!(f, "no intervention required"),
+        }
+    }
+}
+
+/// `PrtAlertGroupTC` values: the printer sub-unit an alert belongs to.
+const ALERT_GROUPS: &[(i64, &str, &str)] = &[
+    (1, "other", "Other"),
+    (3, "host_resources_storage", "Storage"),
+    (4, "host_resources_device", "Device"),
+    (5, "general_printer", "Printer"),
+    (6, "cover", "Cover"),
+    (7, "localization", "Localization"),
+    (8, "input", "Input tray"),
+    (9, "output", "Output bin"),
+    (10, "marker", "Marker"),
+    (11, "marker_supplies", "Supplies"),
+    (12, "marker_colorant", "Colorant"),
+    (13, "media_path", "Media path"),
+    (14, "channel", "Channel"),
+    (15, "interpreter", "Interpreter"),
+    (16, "console_display_buffer", "Console"),
+    (17, "console_lights", "Console lights"),
+    (18, "alert", "Alert"),
+    (30, "finisher_device", "Finisher"),
+    (31, "finisher_supply", "Finisher supply"),
+    (32, "finisher_supply_media_input", "Finisher input"),
+    (33, "finisher_attribute", "Finisher attribute"),
+];
+
+/// `PrtAlertCodeTC` values: what happened.
+const ALERT_CODES: &[(i64, &str, &str)] = &[
+    (1, "other", "Other"),
+    (2, "unknown", "Unknown"),
+    (3, "cover_open", "Cover open"),
+    (4, "cover_closed", "Cover closed"),
+    (5, "interlock_open", "Interlock open"),
+    (6, "interlock_closed", "Interlock closed"),
+    (7, "configuration_change", "Configuration changed"),
+    (8, "jam", "Paper jam"),
+    (9, "subunit_missing", "Missing"),
+    (10, "subunit_life_almost_over", "Life almost over"),
+    (11, "subunit_life_over", "Life over"),
+    (12, "subunit_almost_empty", "Almost empty"),
+    (13, "subunit_empty", "Empty"),
+    (14, "subunit_almost_full", "Almost full"),
+    (15, "subunit_full", "Full"),
+    (16, "subunit_near_limit", "Near limit"),
+    (17, "subunit_at_limit", "At limit"),
+    (18, "subunit_opened", "Opened"),
+    (19, "subunit_closed", "Closed"),
+    (20, "subunit_turned_on", "Turned on"),
+    (21, "subunit_turned_off", "Turned off"),
+    (22, "subunit_offline", "Offline"),
+    (23, "subunit_power_saver", "Power saver"),
+    (24, "subunit_warming_up", "Warming up"),
+    (25, "subunit_added", "Added"),
+    (26, "subunit_removed", "Removed"),
+    (27, "subunit_resource_added", "Resource added"),
+    (28, "subunit_resource_removed", "Resource removed"),
+    (29, "subunit_recoverable_failure", "Recoverable failure"),
+    (30, "subunit_unrecoverable_failure", "Unrecoverable failure"),
+    (
+        31,
+        "subunit_recoverable_storage_error",
+        "Recoverable storage error",
+    ),
+    (
+        32,
+        "subunit_unrecoverable_storage_error",
+        "Unrecoverable storage error",
+    ),
+    (33, "subunit_motor_failure", "Motor failure"),
+    (34, "subunit_memory_exhausted", "Memory exhausted"),
+    (35, "subunit_under_temperature", "Under temperature"),
+    (36, "subunit_over_temperature", "Over temperature"),
+    (37, "subunit_timing_failure", "Timing failure"),
+    (38, "subunit_thermistor_failure", "Thermistor failure"),
+    (501, "door_open", "Door open"),
+    (502, "door_closed", "Door closed"),
+    (503, "powered_up", "Powered up"),
+    (504, "powered_down", "Powered down"),
+    (505, "printer_nms_reset", "Reset by management"),
+    (506, "printer_manual_reset", "Manual reset"),
+    (507, "printer_ready_to_print", "Ready to print"),
+    (801, "input_media_tray_missing", "Input tray missing"),
+    (802, "input_media_size_change", "Input media size changed"),
+    (
+        803,
+        "input_media_weight_change",
+        "Input media weight changed",
+    ),
+    (804, "input_media_type_change", "Input media type changed"),
+    (
+        805,
+        "input_media_color_change",
+        "Input media colour changed",
+    ),
+    (
+        806,
+        "input_media_form_parts_change",
+        "Input form parts changed",
+    ),
+    (807, "input_media_supply_low", "Paper low"),
+    (808, "input_media_supply_empty", "Paper empty"),
+    (809, "input_manual_input_request", "Manual feed requested"),
+    (
+        810,
+        "input_tray_position_failure",
+        "Input tray position failure",
+    ),
+    (
+        811,
+        "input_tray_elevation_failure",
+        "Input tray elevation failure",
+    ),
+    (
+        812,
+        "input_cannot_feed_size_selected",
+        "Cannot feed selected size",
+    ),
+    (901, "output_media_tray_missing", "Output bin missing"),
+    (
+        902,
+        "output_media_tray_almost_full",
+        "Output bin almost full",
+    ),
+    (903, "output_media_tray_full", "Output bin full"),
+    (
+        904,
+        "output_mailbox_select_failure",
+        "Mailbox select failure",
+    ),
+    (
+        1001,
+        "marker_fuser_under_temperature",
+        "Fuser under temperature",
+    ),
+    (
+        1002,
+        "marker_fuser_over_temperature",
+        "Fuser over temperature",
+    ),
+    (1003, "marker_fuser_timing_failure", "Fuser timing failure"),
+    (
+        1004,
+        "marker_fuser_thermistor_failure",
+        "Fuser thermistor failure",
+    ),
+    (
+        1005,
+        "marker_adjusting_print_quality",
+        "Adjusting print quality",
+    ),
+    (1101, "marker_toner_empty", "Toner empty"),
+    (1102, "marker_ink_empty", "Ink empty"),
+    (1103, "marker_print_ribbon_empty", "Ribbon empty"),
+    (1104, "marker_toner_almost_empty", "Toner low"),
+    (1105, "marker_ink_almost_empty", "Ink low"),
+    (1106, "marker_print_ribbon_almost_empty", "Ribbon low"),
+    (
+        1107,
+        "marker_waste_toner_receptacle_almost_full",
+        "Waste toner almost full",
+    ),
+    (
+        1108,
+        "marker_waste_ink_receptacle_almost_full",
+        "Waste ink almost full",
+    ),
+    (
+        1109,
+        "marker_waste_toner_receptacle_full",
+        "Waste toner full",
+    ),
+    (1110, "marker_waste_ink_receptacle_full", "Waste ink full"),
+    (1111, "marker_opc_life_almost_over", "Drum life almost over"),
+    (1112, "marker_opc_life_over", "Drum life over"),
+    (1113, "marker_developer_almost_empty", "Developer low"),
+    (1114, "marker_developer_empty", "Developer empty"),
+    (
+        1115,
+        "marker_toner_cartridge_missing",
+        "Toner cartridge missing",
+    ),
+    (
+        1301,
+        "media_path_media_tray_missing",
+        "Media path tray missing",
+    ),
+    (
+        1302,
+        "media_path_media_tray_almost_full",
+        "Media path tray almost full",
+    ),
+    (1303, "media_path_media_tray_full", "Media path tray full"),
+    (
+        1304,
+        "media_path_cannot_duplex_media_selected",
+        "Cannot duplex selected media",
+    ),
+    (
+        1501,
+        "interpreter_memory_increase",
+        "Interpreter memory increased",
+    ),
+    (
+        1502,
+        "interpreter_memory_decrease",
+        "Interpreter memory decreased",
+    ),
+    (
+        1503,
+        "interpreter_cartridge_added",
+        "Interpreter cartridge added",
+    ),
+    (
+        1504,
+        "interpreter_cartridge_deleted",
+        "Interpreter cartridge removed",
+    ),
+    (
+        1505,
+        "interpreter_resource_added",
+        "Interpreter resource added",
+    ),
+    (
+        1506,
+        "interpreter_resource_deleted",
+        "Interpreter resource removed",
+    ),
+    (
+        1507,
+        "interpreter_resource_unavailable",
+        "Interpreter resource unavailable",
+    ),
+    (
+        1509,
+        "interpreter_complex_page_encountered",
+        "Complex page encountered",
+    ),
+    (
+        1801,
+        "alert_removal_of_binary_change_entry",
+        "Binary change entry removed",
+    ),
+];
+
+/// Looks up a textual convention value, returning its machine and human-readable names.
+fn lookup(table: &'static [(i64, &str, &str)], value: i64) -> Option<(&'static str, &'static str)> {
+    table
+        .iter()
+        .find(|(v, _, _)| *v == value)
+        .map(|(_, name, text)| (*name, *text))
+}
+
+// Group and code are open-ended enumerations, so they keep the raw value and are only
+// named when the value is known. Both serialize as their snake_case name, or the raw
+// value when unknown.
+macro_rules! alert_enum {
+    ($name:ident, $table:ident) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct $name(pub i64);
+
+        impl $name {
+            /// Machine-readable name, e.g. `cover_open`.
+            pub fn name(&self) -> Option<&'static str> {
+                lookup($table, self.0).map(|(name, _)| name)
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match lookup($table, self.0) {
+                    Some((_, text)) => write!(f, "{text}"),
+                    None => write!(f, "#{}", self.0),
+                }
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                match self.name() {
+                    Some(name) => serializer.serialize_str(name),
+                    None => serializer.serialize_i64(self.0),
+                }
+            }
+        }
+    };
+}
+
+alert_enum!(AlertGroup, ALERT_GROUPS);
+alert_enum!(AlertCode, ALERT_CODES);
+
+/// An active alert from the printer's `prtAlertTable`, e.g. a paper jam or an open cover.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PrinterAlert {
+    pub severity: AlertSeverity,
+    pub training_level: AlertTrainingLevel,
+    pub group: AlertGroup,
+    /// Index of the sub-unit within its group, e.g. the tray number.
+    pub group_index: Option<i64>,
+    /// Vendor-specific location code.
+    pub location: Option<i64>,
+    pub code: AlertCode,
+    /// Description as reported by the printer.
+    pub description: String,
+}
+
+impl PrinterAlert {
+    /// Short human-readable message: the printer's description, or the decoded alert code
+    /// when the description is blank.
+    pub fn message(&self) -> String {
+        let description = self.description.trim();
+
+        if description.is_empty() {
+            self.code.to_string()
+        } else {
+            description.to_string()
+        }
+    }
+}
+
+/// Interprets `prtAlertGroupIndex` and `prtAlertLocation`, where negative values mean
+/// "not applicable" or "unknown".
+pub fn optional_index(value: i64) -> Option<i64> {
+    (value >= 0).then_some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_severity_and_training_level() {
+        assert_eq!(AlertSeverity::from_value(3), AlertSeverity::Critical);
+        assert_eq!(AlertSeverity::from_value(4), AlertSeverity::Warning);
+        assert_eq!(AlertSeverity::from_value(9), AlertSeverity::Other);
+        assert_eq!(
+            AlertTrainingLevel::from_value(5),
+            AlertTrainingLevel::FieldService
+        );
+        assert_eq!(
+            AlertTrainingLevel::from_value(0),
+            AlertTrainingLevel::Unknown
+        );
+    }
+
+    #[test]
+    fn names_known_and_unknown_codes() {
+        assert_eq!(AlertCode(8).to_string(), "Paper jam");
+        assert_eq!(AlertCode(8).name(), Some("jam"));
+        assert_eq!(AlertCode(40000).to_string(), "#40000");
+        assert_eq!(AlertGroup(6).to_string(), "Cover");
+    }
+
+    #[test]
+    fn serializes_names_or_raw_values() {
+        let alert = PrinterAlert {
+            severity: AlertSeverity::Critical,
+            training_level: AlertTrainingLevel::Untrained,
+            group: AlertGroup(13),
+            group_index: Some(1),
+            location: None,
+            code: AlertCode(40000),
+            description: " ".to_string(),
+        };
+
+        let json = serde_json::to_value(&alert).unwrap();
+
+        assert_eq!(json["severity"], "critical");
+        assert_eq!(json["group"], "media_path");
+        assert_eq!(json["code"], 40000);
+        assert_eq!(alert.message(), "#40000");
+    }
+}
@@ -1,8 +1,8 @@
 use crate::{
-    driver::{brother::BrotherDriver, generic::GenericDriver},
+    driver::{brother::BrotherDriver, generic::GenericDriver, printer_mib::PrinterMibDriver},
     error::AppError,
     printer::Printer,
-    snmp::SnmpClientParams,
+    snmp::client::SnmpClient,
 };
 use async_trait::async_trait;
 
@@ -18,7 +18,7 @@ pub trait PrinterDriver: Sync + Send {
     /// Executes the SNMP queries required to populate the [`Printer`] data structure for the target device.
     async fn get_supplies(
         &self,
-        params: &SnmpClientParams,
+        client: &SnmpClient,
         printer_name: &str,
     ) -> Result<Printer, AppError>;
 }
@@ -46,16 +46,20 @@ impl DriverManager {
         Self {
             drivers: vec![
                 Box::new(BrotherDriver),
-                Box::new(GenericDriver), // Fallback for other models
+                Box::new(GenericDriver),    // JSON mappings for other models
+                Box::new(PrinterMibDriver), // Fallback when no mapping exists
             ],
         }
     }
 
-    /// Iterates through registered drivers to find the first one compatible with the provided printer name.
-    pub fn get_driver(&self, printer_name: &str) -> Option<&dyn PrinterDriver> {
+    /// Returns the registered drivers compatible with the provided printer name, in priority order.
+    pub fn get_drivers<'a>(
+        &'a self,
+        printer_name: &'a str,
+    ) -> impl Iterator<Item = &'a dyn PrinterDriver> {
         self.drivers
             .iter()
-            .find(|d| d.is_compatible(printer_name))
+            .filter(move |d| d.is_compatible(printer_name))
             .map(|d| d.as_ref())
     }
 }
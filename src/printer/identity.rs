@@ -0,0 +1,128 @@
+use serde::Serialize;
+
+/// Asset details of a device, from the SNMPv2 system group, the Host Resources MIB and
+/// the interfaces table.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Identity {
+    /// `sysName`
+    pub system_name: Option<String>,
+    /// `sysDescr`
+    pub description: Option<String>,
+    /// `sysObjectID`, in dotted notation.
+    pub object_id: Option<String>,
+    /// `sysUpTime`, in seconds.
+    pub uptime_seconds: Option<u64>,
+    /// `sysLocation`
+    pub location: Option<String>,
+    /// `sysContact`
+    pub contact: Option<String>,
+    /// Firmware version (`entPhysicalFirmwareRev`), or the first running software entry
+    /// (`hrSWRunName`) when the agent has no entity table.
+    pub firmware: Option<String>,
+    /// `ifPhysAddress` of the first interface that has one, e.g. `00:1b:a9:12:34:56`.
+    pub mac_address: Option<String>,
+    /// `hrMemorySize`, in kilobytes.
+    pub memory_kb: Option<i64>,
+}
+
+impl Identity {
+    /// Lists the known details as `(label, value)` pairs, in display order.
+    pub fn fields(&self) -> Vec<(&'static str, String)> {
+        [
+            ("System name", self.system_name.clone()),
+            ("Location", self.location.clone()),
+            ("Contact", self.contact.clone()),
+            ("Firmware", self.firmware.clone()),
+            ("MAC address", self.mac_address.clone()),
+            ("Memory", self.memory_kb.map(format_memory)),
+            ("Uptime", self.uptime_seconds.map(format_uptime)),
+            ("Object ID", self.object_id.clone()),
+            ("Description", self.description.clone()),
+        ]
+        .into_iter()
+        .filter_map(|(label, value)| value.map(|v| (label, v)))
+        .collect()
+    }
+}
+
+/// Formats a hardware address as colon-separated hex, e.g. `00:1b:a9:12:34:56`.
+///
+/// Empty and all-zero addresses (loopback or virtual interfaces) give [`None`].
+pub fn format_mac(bytes: &[u8]) -> Option<String> {
+    if bytes.is_empty() || bytes.iter().all(|b| *b == 0) {
+        return None;
+    }
+
+    Some(
+        bytes
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect::<Vec<_>>()
+            .join(":"),
+    )
+}
+
+/// Formats a duration in seconds as e.g. `12d 3h 4m`.
+pub fn format_uptime(seconds: u64) -> String {
+    let days = seconds / 86_400;
+    let hours = seconds % 86_400 / 3_600;
+    let minutes = seconds % 3_600 / 60;
+
+    if days > 0 {
+        format!("{days}d {hours}h {minutes}m")
+    } else {
+        format!("{hours}h {minutes}m")
+    }
+}
+
+/// Formats a memory size in kilobytes as e.g. `512 MB`.
+pub fn format_memory(kilobytes: i64) -> String {
+    if kilobytes >= 1024 {
+        format!("{} MB", kilobytes / 1024)
+    } else {
+        format!("{kilobytes} KB")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_mac_addresses() {
+        assert_eq!(
+            format_mac(&[0x00, 0x1b, 0xa9, 0x12, 0x34, 0x56]).as_deref(),
+            Some("00:1b:a9:12:34:56")
+        );
+        assert_eq!(format_mac(&[0; 6]), None);
+        assert_eq!(format_mac(&[]), None);
+    }
+
+    #[test]
+    fn formats_uptime_and_memory() {
+        assert_eq!(
+            format_uptime(3 * 86_400 + 4 * 3_600 + 5 * 60 + 6),
+            "3d 4h 5m"
+        );
+        assert_eq!(format_uptime(59 * 60), "0h 59m");
+        assert_eq!(format_memory(524_288), "512 MB");
+        assert_eq!(format_memory(640), "640 KB");
+    }
+
+    #[test]
+    fn lists_only_known_fields() {
+        let identity = Identity {
+            location: Some("2nd floor".to_string()),
+            uptime_seconds: Some(90_000),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            identity.fields(),
+            vec![
+                ("Location", "2nd floor".to_string()),
+                ("Uptime", "1d 1h 0m".to_string())
+            ]
+        );
+    }
+}
@@ -1,64 +1,813 @@
-use crate::error::{AppError, ErrorKind};
+use crate::{
+    error::{AppError, ErrorKind},
+    printer::schema::{MatchDefinition, ModelDefinition},
+    utils::parse_oid_to_vec,
+};
 use include_dir::{Dir, include_dir};
+use regex::Regex;
 use serde_json::Value;
-use std::{fs, path::PathBuf};
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
 
 /// A static directory containing printer json files.
 static INTERNAL_DATA_DIR: Dir = include_dir!("assets/data");
 
-/// Loads printer data from a JSON file based on the brand and model.
+/// A place data files are read from.
+#[derive(Debug, Clone)]
+pub enum DataSource {
+    /// The directory given with `--data-dir`, which must exist.
+    Cli(PathBuf),
+    /// The `data` directory next to the configuration file, used when present.
+    User(PathBuf),
+    /// The files bundled into the binary.
+    Bundled,
+}
+
+impl DataSource {
+    /// Lists the sources in precedence order, highest first.
+    pub fn layers(data_dir: Option<PathBuf>) -> Vec<Self> {
+        data_dir
+            .map(Self::Cli)
+            .into_iter()
+            .chain(user_data_dir().map(Self::User))
+            .chain([Self::Bundled])
+            .collect()
+    }
+
+    /// Whether the source has any files to read.
+    pub fn exists(&self) -> bool {
+        match self {
+            Self::Cli(path) | Self::User(path) => path.is_dir(),
+            Self::Bundled => true,
+        }
+    }
+
+    /// Reads the raw text of the JSON files of the source, ordered by file name.
+    ///
+    /// A missing user directory has no files; a missing `--data-dir` is an error.
+    pub fn read_texts(&self) -> Result<Vec<(String, String)>, AppError> {
+        let is_json = |path: &Path| path.extension().and_then(|ext| ext.to_str()) == Some("json");
+
+        let mut files: Vec<(String, String)> = match self {
+            Self::Cli(path) | Self::User(path) => {
+                if !path.is_dir() {
+                    return match self {
+                        Self::Cli(_) => Err(AppError::new(ErrorKind::InvalidDirectory)),
+                        _ => Ok(Vec::new()),
+                    };
+                }
+
+                fs::read_dir(path)
+                    .map_err(|_| AppError::new(ErrorKind::DirectoryRead))?
+                    .filter_map(Result::ok)
+                    .map(|entry| entry.path())
+                    .filter(|path| is_json(path))
+                    .map(|path| Ok((path.display().to_string(), fs::read_to_string(&path)?)))
+                    .collect::<Result<_, AppError>>()?
+            }
+            Self::Bundled => INTERNAL_DATA_DIR
+                .files()
+                .filter(|file| is_json(file.path()))
+                .filter_map(|file| {
+                    let name = file.path().file_name()?.to_str()?;
+                    Some((
+                        format!("<bundled>/{name}"),
+                        file.contents_utf8()?.to_string(),
+                    ))
+                })
+                .collect(),
+        };
+
+        files.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(files)
+    }
+
+    /// Reads and parses the JSON files of the source, ordered by file name.
+    ///
+    /// Any file that is not a JSON object of models is an error.
+    fn read(&self) -> Result<Vec<(String, Value)>, AppError> {
+        self.read_texts()?
+            .into_iter()
+            .map(|(name, json)| {
+                let value = serde_json::from_str::<Value>(&json)
+                    .map_err(|e| AppError::new(ErrorKind::Parse(format!("{name}: {e}"))))?;
+
+                if !value.is_object() {
+                    return Err(AppError::new(ErrorKind::Parse(format!(
+                        "{name}: expected an object of models"
+                    ))));
+                }
+
+                Ok((name, value))
+            })
+            .collect()
+    }
+}
+
+impl Display for DataSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cli(path) => write!(f, "--data-dir {}", path.display()),
+            Self::User(path) => write!(f, "user data {}", path.display()),
+            Self::Bundled => write!(f, "bundled data"),
+        }
+    }
+}
+
+/// Resolves the user data directory, next to the configuration file.
+fn user_data_dir() -> Option<PathBuf> {
+    use directories::ProjectDirs;
+
+    // Windows: %APPDATA%\allansomensi\inkcheck\config\data
+    // Linux: ~/.config/inkcheck/data
+    // macOS: ~/Library/Application Support/com.allansomensi.inkcheck/data
+    ProjectDirs::from("com", "allansomensi", "inkcheck").map(|dirs| dirs.config_dir().join("data"))
+}
+
+/// How the entry of a [`DataEntry`] was chosen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchKind {
+    /// The entry's key is the device description.
+    Key,
+    /// One of the entry's `aliases` is the device description.
+    Alias,
+    /// The entry's key is a glob or regular expression matching the description.
+    Pattern,
+    /// The entry's `match` rules hold for the device.
+    Rules,
+}
+
+impl Display for MatchKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Key => write!(f, "exact key"),
+            Self::Alias => write!(f, "alias"),
+            Self::Pattern => write!(f, "pattern key"),
+            Self::Rules => write!(f, "match rules"),
+        }
+    }
+}
+
+/// The data file entry chosen for a device, with its OIDs resolved.
+#[derive(Debug, Clone)]
+pub struct DataEntry {
+    /// The highest-precedence file declaring the entry, e.g. `<bundled>/xerox.json`.
+    pub file: String,
+    /// Key of the entry within the file.
+    pub key: String,
+    pub matched_by: MatchKind,
+    /// The entry's OID mapping, merged over the entries it `extends`.
+    pub oids: Value,
+    /// The file each mapping came from, keyed by its dotted path, e.g. `toner.black.level`.
+    pub sources: BTreeMap<String, String>,
+}
+
+impl DataEntry {
+    /// Reads the resolved mapping as a typed [`ModelDefinition`].
+    pub fn definition(&self) -> Result<ModelDefinition, AppError> {
+        serde_json::from_value(self.oids.clone()).map_err(|e| {
+            AppError::new(ErrorKind::Parse(format!(
+                "{}: {}: {e}",
+                self.file, self.key
+            )))
+        })
+    }
+
+    /// Lists every mapping with the file it came from.
+    pub fn mappings(&self) -> Vec<DataMapping> {
+        self.sources
+            .iter()
+            .map(|(path, file)| {
+                let value = path
+                    .split('.')
+                    .try_fold(&self.oids, |value, key| value.get(key))
+                    .map(|value| match value {
+                        Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    })
+                    .unwrap_or_default();
+
+                DataMapping {
+                    path: path.clone(),
+                    value,
+                    file: file.clone(),
+                }
+            })
+            .collect()
+    }
+}
+
+impl Display for DataEntry {
+    /// Formats the entry as e.g. `<bundled>/xerox.json: Xerox AltaLink C80* (pattern key)`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} ({})", self.file, self.key, self.matched_by)
+    }
+}
+
+/// A single mapping of a [`DataEntry`], for `--explain`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataMapping {
+    /// Dotted path of the mapping, e.g. `toner.black.level`.
+    pub path: String,
+    /// The mapped OID, or the JSON of a compound value such as `outputs`.
+    pub value: String,
+    /// The file the mapping came from.
+    pub file: String,
+}
+
+/// The rules a data file entry declares under its `match` key, so that it is found by
+/// more than its exact `hrDeviceDescr`:
 ///
-/// This function searches for a JSON file corresponding to the specified printer brand in the given `data_dir` directory.
-/// If no directory is provided, it defaults to using the [`INTERNAL_DATA_DIR`] static directory.
-/// Once a matching file is found, it loads the JSON data and returns the value associated with the specified model.
-pub fn load_printer(
-    brand: &str,
-    model: &str,
-    data_dir: Option<PathBuf>,
-) -> Result<Value, AppError> {
-    let brand_lower = brand.to_lowercase();
+/// ```json
+/// "match": {
+///     "sys_object_id": "1.3.6.1.4.1.2001",
+///     "description": ["\\bB431"],
+///     "aliases": ["OKI B431dn"]
+/// }
+/// ```
+#[derive(Debug, Default)]
+struct MatchRules {
+    /// `sysObjectID` prefix, usually the vendor's enterprise number.
+    object_id: Option<Vec<u64>>,
+    /// Regular expressions tried against `hrDeviceDescr`, compiled once. Invalid ones are
+    /// kept as [`None`] and never match.
+    descriptions: Vec<Option<Regex>>,
+    /// Other descriptions the model is known by, compared ignoring case.
+    aliases: Vec<String>,
+}
 
-    let search_dir = match data_dir {
-        Some(ref path) => path.clone(),
-        None => PathBuf::from(INTERNAL_DATA_DIR.path()),
-    };
+impl MatchRules {
+    /// Reads the `match` key of an entry. Entries without one only match by their key.
+    fn from_entry(entry: &Value) -> Self {
+        let Some(rules) = entry.get("match") else {
+            return Self::default();
+        };
+
+        // Malformed rules are reported by `data validate`; here they just never match.
+        let rules: MatchDefinition = serde_json::from_value(rules.clone()).unwrap_or_default();
+
+        Self {
+            object_id: rules
+                .sys_object_id
+                .and_then(|oid| parse_oid_to_vec(oid.trim_start_matches('.')).ok())
+                .filter(|oid| !oid.is_empty()),
+            descriptions: rules
+                .description
+                .to_vec()
+                .iter()
+                .map(|pattern| Regex::new(pattern).ok())
+                .collect(),
+            aliases: rules.aliases.to_vec(),
+        }
+    }
+
+    /// Whether one of the aliases is the given description.
+    fn matches_alias(&self, description: &str) -> bool {
+        self.aliases
+            .iter()
+            .any(|alias| alias.trim().eq_ignore_ascii_case(description.trim()))
+    }
+
+    /// Whether the device satisfies every declared rule.
+    ///
+    /// An entry without an object ID prefix or description pattern never matches here.
+    /// Patterns that are not valid regular expressions never match.
+    fn matches_rules(&self, description: &str, object_id: Option<&[u64]>) -> bool {
+        if self.object_id.is_none() && self.descriptions.is_empty() {
+            return false;
+        }
+
+        let object_id_matches = match (&self.object_id, object_id) {
+            (None, _) => true,
+            (Some(prefix), Some(oid)) => oid.starts_with(prefix),
+            (Some(_), None) => false,
+        };
+
+        let description_matches = self.descriptions.is_empty()
+            || self
+                .descriptions
+                .iter()
+                .flatten()
+                .any(|regex| regex.is_match(description));
 
-    if data_dir.is_some() && !search_dir.is_dir() {
-        return Err(AppError::new(ErrorKind::InvalidDirectory));
-    }
-
-    if data_dir.is_some() {
-        fs::read_dir(&search_dir)
-            .map_err(|_| AppError::new(ErrorKind::DirectoryRead))?
-            .filter_map(Result::ok)
-            .find(|entry| {
-                entry.path().extension().and_then(|ext| ext.to_str()) == Some("json")
-                    && entry
-                        .path()
-                        .file_stem()
-                        .and_then(|stem| stem.to_str())
-                        .map(|s| s.to_lowercase())
-                        == Some(brand_lower.clone())
+        object_id_matches && description_matches
+    }
+}
+
+/// Turns a pattern key into a case-insensitive regular expression.
+///
+/// Keys wrapped in slashes are regular expressions (`/AltaLink C80\d{2}/`); keys with
+/// `*` or `?` are globs matched against the whole description. Other keys give [`None`].
+fn key_pattern(key: &str) -> Option<Regex> {
+    let pattern = if let Some(regex) = key
+        .strip_prefix('/')
+        .and_then(|k| k.strip_suffix('/'))
+        .filter(|k| !k.is_empty())
+    {
+        format!("(?i){regex}")
+    } else if key.contains(['*', '?']) {
+        let glob: String = key
+            .chars()
+            .map(|c| match c {
+                '*' => ".*".to_string(),
+                '?' => ".".to_string(),
+                c => regex::escape(&c.to_string()),
             })
-            .and_then(|entry| fs::read_to_string(entry.path()).ok())
-            .and_then(|json_str| serde_json::from_str::<Value>(&json_str).ok())
-            .and_then(|json| json.get(model).cloned())
-            .ok_or_else(|| AppError::new(ErrorKind::UnsupportedPrinter(model.to_string())))
+            .collect();
+        format!("(?i)^{glob}$")
     } else {
-        INTERNAL_DATA_DIR
-            .files()
-            .find(|file| {
-                file.path().extension().and_then(|ext| ext.to_str()) == Some("json")
-                    && file
-                        .path()
-                        .file_stem()
-                        .and_then(|stem| stem.to_str())
-                        .map(|s| s.to_lowercase())
-                        == Some(brand_lower.clone())
+        return None;
+    };
+
+    Regex::new(&pattern).ok()
+}
+
+/// Whether `path` is `parent` or lies under it. Every path lies under the root `""`.
+fn is_within(path: &str, parent: &str) -> bool {
+    parent.is_empty()
+        || path == parent
+        || path
+            .strip_prefix(parent)
+            .is_some_and(|rest| rest.starts_with('.'))
+}
+
+/// Records `file` as the source of every leaf of `value` under `path`.
+fn record_sources(value: &Value, sources: &mut BTreeMap<String, String>, file: &str, path: &str) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, value) in map {
+                record_sources(value, sources, file, &join_path(path, key));
+            }
+        }
+        _ => {
+            sources.insert(path.to_string(), file.to_string());
+        }
+    }
+}
+
+fn join_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+/// Overlays `entry` onto `base`: nested objects are merged, other values replaced.
+///
+/// `sources` tracks which file each leaf came from; the leaves of `entry` are attributed
+/// to `file`.
+fn merge(
+    base: &mut Value,
+    entry: &Value,
+    sources: &mut BTreeMap<String, String>,
+    file: &str,
+    path: &str,
+) {
+    if let (Value::Object(base), Value::Object(entry)) = (&mut *base, entry) {
+        for (key, value) in entry {
+            let child = base.entry(key.clone()).or_insert(Value::Null);
+            merge(child, value, sources, file, &join_path(path, key));
+        }
+        return;
+    }
+
+    sources.retain(|p, _| !is_within(p, path));
+    *base = entry.clone();
+    record_sources(entry, sources, file, path);
+}
+
+/// A model as declared across the data sources, merged from the lowest precedence up.
+#[derive(Debug, Default)]
+struct Model {
+    value: Value,
+    sources: BTreeMap<String, String>,
+    /// The highest-precedence file declaring the model.
+    file: String,
+}
+
+/// Merges the models of every file, given from the lowest precedence to the highest, so
+/// that a file only needs to declare the mappings it overrides.
+fn collect_models(files: &[(String, Value)]) -> BTreeMap<String, Model> {
+    let mut models: BTreeMap<String, Model> = BTreeMap::new();
+
+    for (file, json) in files {
+        for (key, entry) in json.as_object().into_iter().flatten() {
+            let model = models.entry(key.clone()).or_default();
+            merge(&mut model.value, entry, &mut model.sources, file, "");
+            model.file = file.clone();
+        }
+    }
+
+    models
+}
+
+/// Resolves a model's `extends` chain into its complete OID mapping and sources.
+///
+/// The `extends` and `match` keys describe the model itself and are not inherited.
+fn resolve_model(
+    models: &BTreeMap<String, Model>,
+    key: &str,
+    chain: &mut Vec<String>,
+) -> Result<(Value, BTreeMap<String, String>), AppError> {
+    let Some(model) = models.get(key) else {
+        return Err(AppError::new(ErrorKind::UnsupportedPrinter(
+            key.to_string(),
+        )));
+    };
+
+    chain.push(key.to_string());
+
+    let (mut value, mut sources) = match model.value.get("extends").and_then(Value::as_str) {
+        Some(parent) => {
+            if chain.iter().any(|k| k == parent) {
+                return Err(AppError::new(ErrorKind::Parse(format!(
+                    "{}: '{key}' extends '{parent}' in a cycle",
+                    model.file
+                ))));
+            }
+
+            if !models.contains_key(parent) {
+                return Err(AppError::new(ErrorKind::Parse(format!(
+                    "{}: '{key}' extends unknown entry '{parent}'",
+                    model.file
+                ))));
+            }
+
+            let (mut value, mut sources) = resolve_model(models, parent, chain)?;
+            merge(&mut value, &model.value, &mut sources, "", "");
+            sources.extend(model.sources.clone());
+            (value, sources)
+        }
+        None => (model.value.clone(), model.sources.clone()),
+    };
+
+    if let Value::Object(map) = &mut value {
+        map.remove("extends");
+        map.remove("match");
+    }
+    sources.retain(|path, _| !is_within(path, "extends") && !is_within(path, "match"));
+
+    Ok((value, sources))
+}
+
+/// Loads the OID mapping of a printer from the JSON data files.
+///
+/// The files of every [`DataSource`] are merged, so the `--data-dir` directory and the
+/// user data directory add models to the bundled ones, and can override single mappings
+/// of a bundled model by declaring it again with only those mappings.
+///
+/// An entry is chosen, in order of preference:
+///
+/// 1. whose key is the device description (`hrDeviceDescr`);
+/// 2. one of whose `aliases` is the description;
+/// 3. whose key is a glob or regular expression matching the description;
+/// 4. whose `match` rules hold: the `sys_object_id` prefix and any `description` pattern.
+///
+/// Within each step entries are searched by key. The chosen entry is merged over the
+/// entries it `extends`, so a model family can declare its common OIDs once.
+///
+/// `object_id` fetches the device's `sysObjectID`. It is only called when the first
+/// three steps fail and a `match` rule declares an object ID prefix, which saves a
+/// request for devices found by name.
+pub async fn load_printer(
+    description: &str,
+    data_dir: Option<PathBuf>,
+    object_id: impl AsyncFnOnce() -> Option<Vec<u64>>,
+) -> Result<DataEntry, AppError> {
+    let mut files = Vec::new();
+    for source in DataSource::layers(data_dir).iter().rev() {
+        files.extend(source.read()?);
+    }
+
+    select_entry(&collect_models(&files), description, object_id).await
+}
+
+/// Chooses and resolves the entry for a device among the merged models, fetching the
+/// object ID only when the `match` rules need it.
+async fn select_entry(
+    models: &BTreeMap<String, Model>,
+    description: &str,
+    object_id: impl AsyncFnOnce() -> Option<Vec<u64>>,
+) -> Result<DataEntry, AppError> {
+    let rules = match_rules(models);
+
+    let found = match find_key(models, &rules, &NAME_MATCHES, description, None) {
+        Some(found) => Some(found),
+        None => {
+            let object_id = if rules.values().any(|r| r.object_id.is_some()) {
+                object_id().await
+            } else {
+                None
+            };
+            find_key(
+                models,
+                &rules,
+                &[MatchKind::Rules],
+                description,
+                object_id.as_deref(),
+            )
+        }
+    };
+
+    resolve_entry(models, found, description)
+}
+
+/// The match kinds that only need the device description.
+const NAME_MATCHES: [MatchKind; 3] = [MatchKind::Key, MatchKind::Alias, MatchKind::Pattern];
+
+/// Reads the `match` rules of every model, built once per lookup rather than once per
+/// match kind.
+fn match_rules(models: &BTreeMap<String, Model>) -> BTreeMap<&str, MatchRules> {
+    models
+        .iter()
+        .map(|(key, model)| (key.as_str(), MatchRules::from_entry(&model.value)))
+        .collect()
+}
+
+/// Finds the first key matching the device by the given kinds, tried in order.
+fn find_key(
+    models: &BTreeMap<String, Model>,
+    rules: &BTreeMap<&str, MatchRules>,
+    kinds: &[MatchKind],
+    description: &str,
+    object_id: Option<&[u64]>,
+) -> Option<(String, MatchKind)> {
+    let matches = |kind: MatchKind, key: &str| match kind {
+        MatchKind::Key => key == description,
+        MatchKind::Alias => rules[key].matches_alias(description),
+        MatchKind::Pattern => key_pattern(key).is_some_and(|regex| regex.is_match(description)),
+        MatchKind::Rules => rules[key].matches_rules(description, object_id),
+    };
+
+    kinds.iter().find_map(|&kind| {
+        models
+            .keys()
+            .find(|key| matches(kind, key))
+            .map(|key| (key.clone(), kind))
+    })
+}
+
+/// Resolves the entry found for a device, or fails when none was.
+fn resolve_entry(
+    models: &BTreeMap<String, Model>,
+    found: Option<(String, MatchKind)>,
+    description: &str,
+) -> Result<DataEntry, AppError> {
+    let (key, matched_by) = found
+        .ok_or_else(|| AppError::new(ErrorKind::UnsupportedPrinter(description.to_string())))?;
+
+    let (oids, sources) = resolve_model(models, &key, &mut Vec::new())?;
+
+    Ok(DataEntry {
+        file: models[&key].file.clone(),
+        key,
+        matched_by,
+        oids,
+        sources,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Chooses and resolves the entry for a device, trying every match kind at once.
+    fn find_entry(
+        models: &BTreeMap<String, Model>,
+        description: &str,
+        object_id: Option<&[u64]>,
+    ) -> Result<DataEntry, AppError> {
+        let kinds = [
+            MatchKind::Key,
+            MatchKind::Alias,
+            MatchKind::Pattern,
+            MatchKind::Rules,
+        ];
+        let found = find_key(models, &match_rules(models), &kinds, description, object_id);
+
+        resolve_entry(models, found, description)
+    }
+
+    const OKI_OBJECT_ID: &[u64] = &[1, 3, 6, 1, 4, 1, 2001, 1, 1, 1, 1, 100, 3, 1];
+
+    #[test]
+    fn rules_need_every_declared_criterion() {
+        let rules = MatchRules::from_entry(&json!({
+            "match": { "sys_object_id": "1.3.6.1.4.1.2001", "description": "\\bB431" }
+        }));
+
+        assert!(rules.matches_rules("OKI B431", Some(OKI_OBJECT_ID)));
+        assert!(!rules.matches_rules("OKI B432", Some(OKI_OBJECT_ID)));
+        assert!(!rules.matches_rules("OKI B431", Some(&[1, 3, 6, 1, 4, 1, 1248])));
+        assert!(!rules.matches_rules("OKI B431", None));
+    }
+
+    #[test]
+    fn entries_without_rules_only_match_by_name() {
+        let rules = MatchRules::from_entry(&json!({ "match": { "aliases": ["B431dn"] } }));
+
+        assert!(rules.matches_alias(" b431DN "));
+        assert!(!rules.matches_rules("B431dn", Some(OKI_OBJECT_ID)));
+        assert!(!MatchRules::from_entry(&json!({})).matches_rules("B431", None));
+    }
+
+    #[test]
+    fn invalid_patterns_never_match() {
+        let rules = MatchRules::from_entry(&json!({ "match": { "description": ["B431("] } }));
+
+        assert!(!rules.matches_rules("B431(", None));
+    }
+
+    #[test]
+    fn pattern_keys_are_globs_or_regexes() {
+        let glob = key_pattern("Xerox AltaLink C80*").unwrap();
+        assert!(glob.is_match("xerox altalink C8045"));
+        assert!(!glob.is_match("Xerox VersaLink C8000"));
+
+        let regex = key_pattern("/AltaLink C80\\d{2}/").unwrap();
+        assert!(regex.is_match("Xerox AltaLink C8035 Multifunction Printer"));
+
+        assert!(key_pattern("B431").is_none());
+        assert!(key_pattern("/").is_none());
+    }
+
+    fn models(files: &[(&str, Value)]) -> BTreeMap<String, Model> {
+        let files: Vec<(String, Value)> = files
+            .iter()
+            .map(|(name, json)| (name.to_string(), json.clone()))
+            .collect();
+        collect_models(&files)
+    }
+
+    #[test]
+    fn entries_are_merged_over_their_parents() {
+        let models = models(&[(
+            "family.json",
+            json!({
+                "Family": {
+                    "match": { "aliases": ["Family"] },
+                    "toner": { "black": { "level": "1.1", "max_level": "1.2" } },
+                    "metrics": { "total_impressions": "1.9" }
+                },
+                "Model": {
+                    "extends": "Family",
+                    "toner": { "black": { "level": "2.1" } }
+                }
+            }),
+        )]);
+
+        let (model, sources) = resolve_model(&models, "Model", &mut Vec::new()).unwrap();
+
+        assert_eq!(
+            model,
+            json!({
+                "toner": { "black": { "level": "2.1", "max_level": "1.2" } },
+                "metrics": { "total_impressions": "1.9" }
             })
-            .and_then(|file| file.contents_utf8())
-            .and_then(|json_str| serde_json::from_str::<Value>(json_str).ok())
-            .and_then(|json| json.get(model).cloned())
-            .ok_or_else(|| AppError::new(ErrorKind::UnsupportedPrinter(model.to_string())))
+        );
+        assert_eq!(sources.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn object_id_is_only_fetched_for_match_rules() {
+        let models = models(&[(
+            "oki.json",
+            json!({
+                "B431": { "match": { "aliases": ["OKI B431dn"] } },
+                "OKI": { "match": { "sys_object_id": "1.3.6.1.4.1.2001" } }
+            }),
+        )]);
+        let fetches = std::sync::atomic::AtomicUsize::new(0);
+        let object_id = async || {
+            fetches.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            Some(OKI_OBJECT_ID.to_vec())
+        };
+
+        let entry = select_entry(&models, "OKI B431dn", object_id)
+            .await
+            .unwrap();
+        assert_eq!(
+            (entry.key.as_str(), entry.matched_by),
+            ("B431", MatchKind::Alias)
+        );
+        assert_eq!(fetches.load(std::sync::atomic::Ordering::Relaxed), 0);
+
+        let entry = select_entry(&models, "OKI C332", object_id).await.unwrap();
+        assert_eq!(
+            (entry.key.as_str(), entry.matched_by),
+            ("OKI", MatchKind::Rules)
+        );
+        assert_eq!(fetches.load(std::sync::atomic::Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn higher_layers_override_single_mappings() {
+        let models = models(&[
+            (
+                "<bundled>/oki.json",
+                json!({
+                    "B431": {
+                        "info": { "serial_number": "1.1" },
+                        "toner": { "black": { "level": "1.2", "max_level": "1.3" } }
+                    }
+                }),
+            ),
+            (
+                "/home/me/data/oki.json",
+                json!({ "B431": { "info": { "serial_number": "2.1" } } }),
+            ),
+        ]);
+
+        let entry = find_entry(&models, "B431", None).unwrap();
+
+        assert_eq!(entry.file, "/home/me/data/oki.json");
+        assert_eq!(entry.oids["info"]["serial_number"], "2.1");
+        assert_eq!(entry.oids["toner"]["black"]["level"], "1.2");
+        assert_eq!(
+            entry.mappings()[0],
+            DataMapping {
+                path: "info.serial_number".to_string(),
+                value: "2.1".to_string(),
+                file: "/home/me/data/oki.json".to_string(),
+            }
+        );
+        assert_eq!(entry.sources["toner.black.level"], "<bundled>/oki.json");
+    }
+
+    #[test]
+    fn extends_cycles_and_unknown_parents_are_errors() {
+        let models = models(&[(
+            "loop.json",
+            json!({
+                "A": { "extends": "B" },
+                "B": { "extends": "A" },
+                "C": { "extends": "Missing" }
+            }),
+        )]);
+
+        assert!(resolve_model(&models, "A", &mut Vec::new()).is_err());
+        assert!(matches!(
+            resolve_model(&models, "C", &mut Vec::new())
+                .unwrap_err()
+                .kind(),
+            ErrorKind::Parse(_)
+        ));
+    }
+
+    #[test]
+    fn missing_cli_directory_is_an_error() {
+        let source = DataSource::Cli(PathBuf::from("/nonexistent/inkcheck-data"));
+
+        assert!(matches!(
+            source.read().unwrap_err().kind(),
+            ErrorKind::InvalidDirectory
+        ));
+        assert!(
+            DataSource::User(PathBuf::from("/nonexistent/inkcheck-data"))
+                .read()
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    /// Looks a device up in the bundled data only, so the user's data directory on the
+    /// machine running the tests cannot change the result.
+    fn find_bundled(description: &str, object_id: Option<&[u64]>) -> Result<DataEntry, AppError> {
+        let models = collect_models(&DataSource::Bundled.read().unwrap());
+        find_entry(&models, description, object_id)
+    }
+
+    #[test]
+    fn bundled_files_match_by_key_and_rules() {
+        let by_key = find_bundled("EPSON WF-C5790 Series", None).unwrap();
+        assert_eq!(by_key.matched_by, MatchKind::Key);
+        assert!(by_key.oids.get("toner").is_some());
+
+        let by_alias = find_bundled("OKI B431dn", None).unwrap();
+        assert_eq!(by_alias.to_string(), "<bundled>/B431.json: B431 (alias)");
+
+        let by_rules = find_bundled("OKI B431 PS", Some(OKI_OBJECT_ID)).unwrap();
+        assert_eq!(by_rules.matched_by, MatchKind::Rules);
+        assert!(by_rules.oids.get("match").is_none());
+
+        assert!(matches!(
+            find_bundled("OKI B431 PS", None).unwrap_err().kind(),
+            ErrorKind::UnsupportedPrinter(_)
+        ));
+    }
+
+    #[test]
+    fn bundled_model_families_inherit_oids() {
+        let entry = find_bundled("Xerox AltaLink C8045 Multifunction Printer", None).unwrap();
+
+        assert_eq!(entry.matched_by, MatchKind::Pattern);
+        assert!(entry.oids.get("drum").is_some());
+        assert!(entry.oids.get("extends").is_none());
     }
 }
@@ -1,12 +1,31 @@
+use crate::history::forecast::SupplyForecast;
+use crate::printer::alert::PrinterAlert;
+use crate::printer::identity::Identity;
+use crate::printer::load::DataMapping;
+use crate::printer::output::OutputBin;
+use crate::printer::status::{DeviceStatus, PrinterError, PrinterStatus};
 use crate::printer::supply::{
-    CalculateLevel, drum::Drums, fuser::Fuser, reservoir::Reservoir, toner::Toners,
+    CalculateLevel, PrinterSupply, Supply,
+    drum::{Drum, Drums},
+    fuser::Fuser,
+    reservoir::Reservoir,
+    toner::{Toner, TonerColor, Toners},
 };
-use serde::Serialize;
+use crate::printer::tray::Tray;
+use serde::{Serialize, Serializer};
 
+pub mod alert;
 pub mod driver;
+pub mod identity;
 pub mod load;
+pub mod output;
 pub mod scan;
+pub mod schema;
+pub mod status;
 pub mod supply;
+pub mod transform;
+pub mod tray;
+pub mod validate;
 
 /// Tracks printing usage statistics including total, monochrome, and color impressions.
 #[derive(Serialize, Debug, Default, Clone, Copy)]
@@ -16,22 +35,66 @@ pub struct Metrics {
     pub color_impressions: Option<i64>,
 }
 
+/// The standard CMYK slots of the legacy [`Toners`] and [`Drums`] structs.
+const CMYK: [TonerColor; 4] = [
+    TonerColor::Black,
+    TonerColor::Cyan,
+    TonerColor::Magenta,
+    TonerColor::Yellow,
+];
+
+// The legacy supply structs share the same level fields, so a macro converts
+// them to and from a [`Supply`] regardless of the specific type.
+macro_rules! to_supply {
+    ($kind:expr, $item:expr) => {{
+        let item = $item;
+        let mut supply = Supply::new($kind, item.level, item.max_level, item.level_percent);
+        supply.state = item.state;
+        supply
+    }};
+}
+
+macro_rules! from_supply {
+    ($type:ident, $supply:expr) => {{
+        let supply = $supply;
+        $type {
+            level: supply.level,
+            max_level: supply.max_level,
+            level_percent: supply.level_percent,
+            state: supply.state,
+        }
+    }};
+}
+
 /// Represents the comprehensive state of a printer.
 ///
-/// Aggregates identity, consumable supplies, and usage metrics.
-#[derive(Serialize, Debug)]
+/// Aggregates identity, operational status, console message, consumable supplies, paper trays, output
+/// bins, usage metrics and active alerts.
+#[derive(Debug)]
 pub struct Printer {
     pub name: String,
     pub serial_number: Option<String>,
-    pub toners: Toners,
-    pub drums: Drums,
-    pub fuser: Option<Fuser>,
-    pub reservoir: Option<Reservoir>,
+    pub identity: Option<Identity>,
+    pub status: Option<PrinterStatus>,
+    pub device_status: Option<DeviceStatus>,
+    pub errors: Vec<PrinterError>,
+    /// Text shown on the front-panel display, one line after another.
+    pub console_message: Option<String>,
+    pub supplies: Vec<Supply>,
+    pub trays: Vec<Tray>,
+    pub outputs: Vec<OutputBin>,
     pub metrics: Option<Metrics>,
+    pub alerts: Vec<PrinterAlert>,
+    pub forecasts: Vec<SupplyForecast>,
+    /// Data file entry the OIDs were loaded from, e.g. `xerox.json: Xerox AltaLink C80*
+    /// Multifunction Printer (pattern key)`.
+    pub data_source: Option<String>,
+    /// The OID mappings of the data file entry and the file each came from.
+    pub data_mappings: Vec<DataMapping>,
 }
 
 impl Printer {
-    /// Creates a new [`Printer`] instance.
+    /// Creates a new [`Printer`] instance from the fixed CMYK supply slots.
     pub fn new(
         name: String,
         serial_number: Option<String>,
@@ -40,41 +103,212 @@ impl Printer {
         fuser: Option<Fuser>,
         reservoir: Option<Reservoir>,
         metrics: Option<Metrics>,
+    ) -> Self {
+        let toner_slots = [
+            toners.black_toner,
+            toners.cyan_toner,
+            toners.magenta_toner,
+            toners.yellow_toner,
+        ];
+        let drum_slots = [
+            drums.black_drum,
+            drums.cyan_drum,
+            drums.magenta_drum,
+            drums.yellow_drum,
+        ];
+
+        let mut supplies = Vec::new();
+
+        for (color, toner) in CMYK.into_iter().zip(toner_slots) {
+            if let Some(toner) = toner {
+                supplies
+                    .push(to_supply!(PrinterSupply::Toner, toner).with_color(color.to_string()));
+            }
+        }
+
+        for (color, drum) in CMYK.into_iter().zip(drum_slots) {
+            if let Some(drum) = drum {
+                supplies.push(to_supply!(PrinterSupply::Drum, drum).with_color(color.to_string()));
+            }
+        }
+
+        supplies.extend(fuser.map(|f| to_supply!(PrinterSupply::Fuser, f)));
+        supplies.extend(reservoir.map(|r| to_supply!(PrinterSupply::Reservoir, r)));
+
+        Self::from_supplies(name, serial_number, supplies, metrics)
+    }
+
+    /// Creates a new [`Printer`] instance from a list of supplies.
+    pub fn from_supplies(
+        name: String,
+        serial_number: Option<String>,
+        supplies: Vec<Supply>,
+        metrics: Option<Metrics>,
     ) -> Self {
         Self {
             name,
             serial_number,
-            toners,
-            drums,
-            fuser,
-            reservoir,
+            identity: None,
+            status: None,
+            device_status: None,
+            errors: Vec::new(),
+            console_message: None,
+            supplies,
+            trays: Vec::new(),
+            outputs: Vec::new(),
             metrics,
+            alerts: Vec::new(),
+            forecasts: Vec::new(),
+            data_source: None,
+            data_mappings: Vec::new(),
         }
     }
 
     /// Iterates over all attached supply components and calculates their remaining life percentage.
     pub fn calculate_all_levels(&mut self) {
-        // Toners
-        self.toners.black_toner.calculate_level_percent();
-        self.toners.cyan_toner.calculate_level_percent();
-        self.toners.magenta_toner.calculate_level_percent();
-        self.toners.yellow_toner.calculate_level_percent();
-
-        // Drums
-        self.drums.black_drum.calculate_level_percent();
-        self.drums.cyan_drum.calculate_level_percent();
-        self.drums.magenta_drum.calculate_level_percent();
-        self.drums.yellow_drum.calculate_level_percent();
-
-        // Maintenance
-        self.fuser.calculate_level_percent();
-        self.reservoir.calculate_level_percent();
+        for supply in &mut self.supplies {
+            supply.calculate_level_percent();
+        }
+    }
+
+    /// Short description of the operational status, e.g. `Idle, Door open, Jammed`.
+    ///
+    /// The device status is only mentioned when it is not `running`. Returns [`None`] when
+    /// the printer reported no status at all.
+    pub fn status_summary(&self) -> Option<String> {
+        let mut parts: Vec<String> = Vec::new();
+
+        parts.extend(self.status.map(|s| s.to_string()));
+        parts.extend(
+            self.device_status
+                .filter(|s| *s != DeviceStatus::Running)
+                .map(|s| s.to_string()),
+        );
+        parts.extend(self.errors.iter().map(|e| e.to_string()));
+
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+
+    /// Finds the depletion forecast of a supply by its label, e.g. `Black Toner`.
+    pub fn forecast(&self, supply: &str) -> Option<&SupplyForecast> {
+        self.forecasts.iter().find(|f| f.supply == supply)
+    }
+
+    /// Lists the supplies of the given kind, in the order the printer reported them.
+    pub fn supplies_of(&self, kind: PrinterSupply) -> impl Iterator<Item = &Supply> {
+        self.supplies.iter().filter(move |s| s.kind == kind)
+    }
+
+    /// Finds the first supply of the given kind and colour.
+    fn find(&self, kind: PrinterSupply, color: TonerColor) -> Option<&Supply> {
+        self.supplies_of(kind)
+            .find(|s| s.is_color(&color.to_string()))
+    }
+
+    /// The CMYK toners, as exposed by the legacy JSON fields.
+    pub fn toners(&self) -> Toners {
+        let [black, cyan, magenta, yellow] = CMYK.map(|c| {
+            self.find(PrinterSupply::Toner, c)
+                .map(|s| from_supply!(Toner, s))
+        });
+
+        Toners {
+            black_toner: black,
+            cyan_toner: cyan,
+            magenta_toner: magenta,
+            yellow_toner: yellow,
+        }
+    }
+
+    /// The CMYK drums, as exposed by the legacy JSON fields.
+    pub fn drums(&self) -> Drums {
+        let [black, cyan, magenta, yellow] = CMYK.map(|c| {
+            self.find(PrinterSupply::Drum, c)
+                .map(|s| from_supply!(Drum, s))
+        });
+
+        Drums {
+            black_drum: black,
+            cyan_drum: cyan,
+            magenta_drum: magenta,
+            yellow_drum: yellow,
+        }
+    }
+
+    /// The first fuser, as exposed by the legacy JSON field.
+    pub fn fuser(&self) -> Option<Fuser> {
+        self.supplies_of(PrinterSupply::Fuser)
+            .next()
+            .map(|s| from_supply!(Fuser, s))
+    }
+
+    /// The first reservoir, as exposed by the legacy JSON field.
+    pub fn reservoir(&self) -> Option<Reservoir> {
+        self.supplies_of(PrinterSupply::Reservoir)
+            .next()
+            .map(|s| from_supply!(Reservoir, s))
+    }
+}
+
+impl Serialize for Printer {
+    /// Serializes the supply list along with the legacy `toners`, `drums`, `fuser` and
+    /// `reservoir` fields, so existing JSON consumers keep working.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct PrinterJson<'a> {
+            name: &'a str,
+            serial_number: &'a Option<String>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            identity: &'a Option<Identity>,
+            status: Option<PrinterStatus>,
+            device_status: Option<DeviceStatus>,
+            errors: &'a [PrinterError],
+            console_message: &'a Option<String>,
+            toners: Toners,
+            drums: Drums,
+            fuser: Option<Fuser>,
+            reservoir: Option<Reservoir>,
+            supplies: &'a [Supply],
+            #[serde(skip_serializing_if = "<[_]>::is_empty")]
+            trays: &'a [Tray],
+            #[serde(skip_serializing_if = "<[_]>::is_empty")]
+            outputs: &'a [OutputBin],
+            metrics: &'a Option<Metrics>,
+            alerts: &'a [PrinterAlert],
+            #[serde(skip_serializing_if = "<[_]>::is_empty")]
+            forecasts: &'a [SupplyForecast],
+            #[serde(skip_serializing_if = "Option::is_none")]
+            data_source: &'a Option<String>,
+        }
+
+        PrinterJson {
+            name: &self.name,
+            serial_number: &self.serial_number,
+            identity: &self.identity,
+            status: self.status,
+            device_status: self.device_status,
+            errors: &self.errors,
+            console_message: &self.console_message,
+            toners: self.toners(),
+            drums: self.drums(),
+            fuser: self.fuser(),
+            reservoir: self.reservoir(),
+            supplies: &self.supplies,
+            trays: &self.trays,
+            outputs: &self.outputs,
+            metrics: &self.metrics,
+            alerts: &self.alerts,
+            forecasts: &self.forecasts,
+            data_source: &self.data_source,
+        }
+        .serialize(serializer)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::Printer;
+    use crate::printer::status::{DeviceStatus, PrinterError, PrinterStatus};
     use crate::printer::{
         Drums, Toners,
         supply::{drum::Drum, fuser::Fuser, reservoir::Reservoir, toner::Toner},
@@ -96,11 +330,11 @@ mod tests {
         );
 
         assert_eq!(printer.name, "Constructor Test");
-        assert_eq!(printer.serial_number.unwrap(), "XYZ-123");
-        assert!(printer.toners.black_toner.is_some());
-        assert!(printer.drums.black_drum.is_none());
-        assert!(printer.fuser.is_some());
-        assert!(printer.reservoir.is_none());
+        assert_eq!(printer.serial_number.as_deref().unwrap(), "XYZ-123");
+        assert!(printer.toners().black_toner.is_some());
+        assert!(printer.drums().black_drum.is_none());
+        assert!(printer.fuser().is_some());
+        assert!(printer.reservoir().is_none());
     }
 
     #[test]
@@ -122,13 +356,19 @@ mod tests {
 
         printer.calculate_all_levels();
 
-        assert_eq!(printer.toners.black_toner.unwrap().level_percent, Some(80));
-        assert_eq!(printer.toners.cyan_toner.unwrap().level_percent, Some(33));
         assert_eq!(
-            printer.toners.magenta_toner.unwrap().level_percent,
+            printer.toners().black_toner.unwrap().level_percent,
+            Some(80)
+        );
+        assert_eq!(printer.toners().cyan_toner.unwrap().level_percent, Some(33));
+        assert_eq!(
+            printer.toners().magenta_toner.unwrap().level_percent,
             Some(66)
         );
-        assert_eq!(printer.toners.yellow_toner.unwrap().level_percent, Some(10));
+        assert_eq!(
+            printer.toners().yellow_toner.unwrap().level_percent,
+            Some(10)
+        );
     }
 
     #[test]
@@ -150,10 +390,13 @@ mod tests {
 
         printer.calculate_all_levels();
 
-        assert_eq!(printer.drums.black_drum.unwrap().level_percent, Some(80));
-        assert_eq!(printer.drums.cyan_drum.unwrap().level_percent, Some(33));
-        assert_eq!(printer.drums.magenta_drum.unwrap().level_percent, Some(66));
-        assert_eq!(printer.drums.yellow_drum.unwrap().level_percent, Some(10));
+        assert_eq!(printer.drums().black_drum.unwrap().level_percent, Some(80));
+        assert_eq!(printer.drums().cyan_drum.unwrap().level_percent, Some(33));
+        assert_eq!(
+            printer.drums().magenta_drum.unwrap().level_percent,
+            Some(66)
+        );
+        assert_eq!(printer.drums().yellow_drum.unwrap().level_percent, Some(10));
     }
 
     #[test]
@@ -170,7 +413,7 @@ mod tests {
 
         printer.calculate_all_levels();
 
-        assert_eq!(printer.fuser.unwrap().level_percent, Some(75));
+        assert_eq!(printer.fuser().unwrap().level_percent, Some(75));
     }
 
     #[test]
@@ -187,7 +430,7 @@ mod tests {
 
         printer.calculate_all_levels();
 
-        assert_eq!(printer.reservoir.unwrap().level_percent, Some(80));
+        assert_eq!(printer.reservoir().unwrap().level_percent, Some(80));
     }
 
     #[test]
@@ -210,7 +453,7 @@ mod tests {
 
         assert!(
             printer
-                .toners
+                .toners()
                 .black_toner
                 .as_ref()
                 .unwrap()
@@ -219,7 +462,7 @@ mod tests {
         );
         assert!(
             printer
-                .drums
+                .drums()
                 .black_drum
                 .as_ref()
                 .unwrap()
@@ -229,9 +472,29 @@ mod tests {
 
         printer.calculate_all_levels();
 
-        assert_eq!(printer.toners.black_toner.unwrap().level_percent, Some(50));
-        assert_eq!(printer.drums.black_drum.unwrap().level_percent, Some(40));
-        assert_eq!(printer.fuser.unwrap().level_percent, Some(30));
-        assert_eq!(printer.reservoir.unwrap().level_percent, Some(20));
+        assert_eq!(
+            printer.toners().black_toner.unwrap().level_percent,
+            Some(50)
+        );
+        assert_eq!(printer.drums().black_drum.unwrap().level_percent, Some(40));
+        assert_eq!(printer.fuser().unwrap().level_percent, Some(30));
+        assert_eq!(printer.reservoir().unwrap().level_percent, Some(20));
+    }
+
+    #[test]
+    fn test_status_summary() {
+        let mut printer = Printer::from_supplies("Status Test".to_string(), None, vec![], None);
+        assert_eq!(printer.status_summary(), None);
+
+        printer.status = Some(PrinterStatus::Idle);
+        printer.device_status = Some(DeviceStatus::Running);
+        assert_eq!(printer.status_summary().as_deref(), Some("Idle"));
+
+        printer.device_status = Some(DeviceStatus::Warning);
+        printer.errors = vec![PrinterError::DoorOpen, PrinterError::Jammed];
+        assert_eq!(
+            printer.status_summary().as_deref(),
+            Some("Idle, Warning, Door open, Jammed")
+        );
     }
 }
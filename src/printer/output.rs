@@ -0,0 +1,61 @@
+use crate::printer::{status::SubunitStatus, supply::SupplyState};
+use serde::Serialize;
+
+/// An output bin from the `prtOutputTable`, or from a vendor mapping.
+///
+/// Unlike supplies and trays, the level of a bin is the space left in it, so a bin at
+/// 0% is full.
+#[derive(Debug, Clone, Serialize)]
+pub struct OutputBin {
+    /// Bin name as reported by the printer, e.g. `Face Down Bin`.
+    pub name: String,
+    pub remaining_capacity: i64,
+    pub max_capacity: i64,
+    /// Free space left, in percent.
+    pub remaining_percent: Option<i64>,
+    pub state: SupplyState,
+    pub status: Option<SubunitStatus>,
+}
+
+impl OutputBin {
+    /// Creates a new [`OutputBin`], interpreting the capacities like a supply's levels.
+    pub fn new(name: String, remaining_capacity: i64, max_capacity: i64) -> Self {
+        let state = SupplyState::from_levels(remaining_capacity, max_capacity);
+
+        Self {
+            name,
+            remaining_capacity,
+            max_capacity,
+            remaining_percent: state.percent(),
+            state,
+            status: None,
+        }
+    }
+
+    /// Whether the bin has no space left.
+    pub fn is_full(&self) -> bool {
+        self.state == SupplyState::Exact(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_capacity_means_full() {
+        let bin = OutputBin::new("Face Down Bin".to_string(), 0, 250);
+
+        assert!(bin.is_full());
+        assert_eq!(bin.remaining_percent, Some(0));
+    }
+
+    #[test]
+    fn special_capacities_are_not_full() {
+        let bin = OutputBin::new("Stacker".to_string(), -3, 500);
+
+        assert!(!bin.is_full());
+        assert_eq!(bin.state, SupplyState::SomeRemaining);
+        assert_eq!(bin.remaining_percent, None);
+    }
+}
@@ -0,0 +1,248 @@
+use crate::printer::{supply::toner::TonerColor, transform::ValueDefinition};
+use serde::Deserialize;
+
+/// A model entry of a JSON data file: the OIDs to query for one printer model.
+///
+/// Every section is optional, since an entry that `extends` another one only declares
+/// what it overrides. Unknown keys are rejected so that typos are reported by
+/// `inkcheck data validate` instead of being silently ignored.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModelDefinition {
+    /// Key of the entry this one is merged over.
+    pub extends: Option<String>,
+    /// Rules to find the entry by more than its key.
+    #[serde(rename = "match")]
+    pub match_rules: Option<MatchDefinition>,
+    pub info: Option<InfoDefinition>,
+    pub toner: Option<ColorSupplies>,
+    pub drum: Option<ColorSupplies>,
+    pub fuser: Option<SupplyDefinition>,
+    pub reservoir: Option<SupplyDefinition>,
+    pub metrics: Option<MetricsDefinition>,
+    pub outputs: Option<Vec<OutputDefinition>>,
+}
+
+/// The `match` section of a model entry.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MatchDefinition {
+    /// `sysObjectID` prefix, e.g. `1.3.6.1.4.1.2001`.
+    pub sys_object_id: Option<String>,
+    /// Regular expressions tried against `hrDeviceDescr`.
+    #[serde(default)]
+    pub description: StringList,
+    /// Other descriptions the model is known by.
+    #[serde(default)]
+    pub aliases: StringList,
+}
+
+/// A single string or a list of strings.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum StringList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Default for StringList {
+    fn default() -> Self {
+        Self::Many(Vec::new())
+    }
+}
+
+impl StringList {
+    /// The strings, in order.
+    pub fn to_vec(&self) -> Vec<String> {
+        match self {
+            Self::One(s) => vec![s.clone()],
+            Self::Many(items) => items.clone(),
+        }
+    }
+}
+
+/// The `info` section of a model entry.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InfoDefinition {
+    pub serial_number: Option<String>,
+}
+
+/// A supply's current and maximum level.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SupplyDefinition {
+    pub level: Option<ValueDefinition>,
+    pub max_level: Option<ValueDefinition>,
+}
+
+/// The `toner` or `drum` section of a model entry, one supply per CMYK colour.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ColorSupplies {
+    pub black: Option<SupplyDefinition>,
+    pub cyan: Option<SupplyDefinition>,
+    pub magenta: Option<SupplyDefinition>,
+    pub yellow: Option<SupplyDefinition>,
+}
+
+impl ColorSupplies {
+    /// The supply of the given colour.
+    pub fn get(&self, color: TonerColor) -> Option<&SupplyDefinition> {
+        match color {
+            TonerColor::Black => self.black.as_ref(),
+            TonerColor::Cyan => self.cyan.as_ref(),
+            TonerColor::Magenta => self.magenta.as_ref(),
+            TonerColor::Yellow => self.yellow.as_ref(),
+        }
+    }
+}
+
+/// The `metrics` section of a model entry.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MetricsDefinition {
+    pub total_impressions: Option<ValueDefinition>,
+    pub mono_impressions: Option<ValueDefinition>,
+    pub color_impressions: Option<ValueDefinition>,
+}
+
+/// An entry of the `outputs` section of a model entry.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OutputDefinition {
+    pub name: String,
+    pub remaining_capacity: String,
+    pub max_capacity: String,
+    /// `prtOutputStatus`, optional.
+    pub status: Option<String>,
+}
+
+impl ModelDefinition {
+    /// Lists every OID of the entry with its dotted path, e.g. `toner.black.level`.
+    pub fn oids(&self) -> Vec<(String, &str)> {
+        let mut oids: Vec<(String, Option<&str>)> = Vec::new();
+
+        if let Some(rules) = &self.match_rules {
+            oids.push((
+                "match.sys_object_id".to_string(),
+                rules.sys_object_id.as_deref(),
+            ));
+        }
+
+        if let Some(info) = &self.info {
+            oids.push((
+                "info.serial_number".to_string(),
+                info.serial_number.as_deref(),
+            ));
+        }
+
+        let colors = [
+            ("black", TonerColor::Black),
+            ("cyan", TonerColor::Cyan),
+            ("magenta", TonerColor::Magenta),
+            ("yellow", TonerColor::Yellow),
+        ];
+
+        let color_supplies = [("toner", &self.toner), ("drum", &self.drum)]
+            .into_iter()
+            .flat_map(|(section, supplies)| {
+                colors.into_iter().filter_map(move |(name, color)| {
+                    let supply = supplies.as_ref()?.get(color)?;
+                    Some((format!("{section}.{name}"), supply))
+                })
+            });
+
+        let single_supplies = [("fuser", &self.fuser), ("reservoir", &self.reservoir)]
+            .into_iter()
+            .filter_map(|(section, supply)| Some((section.to_string(), supply.as_ref()?)));
+
+        let mut values: Vec<(String, &ValueDefinition)> = Vec::new();
+
+        for (path, supply) in color_supplies.chain(single_supplies) {
+            values.extend(supply.level.as_ref().map(|v| (format!("{path}.level"), v)));
+            values.extend(
+                supply
+                    .max_level
+                    .as_ref()
+                    .map(|v| (format!("{path}.max_level"), v)),
+            );
+        }
+
+        if let Some(metrics) = &self.metrics {
+            for (name, value) in [
+                ("total_impressions", &metrics.total_impressions),
+                ("mono_impressions", &metrics.mono_impressions),
+                ("color_impressions", &metrics.color_impressions),
+            ] {
+                values.extend(value.as_ref().map(|v| (format!("metrics.{name}"), v)));
+            }
+        }
+
+        for (path, value) in values {
+            for (suffix, oid) in value.oids() {
+                oids.push((format!("{path}{suffix}"), Some(oid)));
+            }
+        }
+
+        for (index, output) in self.outputs.iter().flatten().enumerate() {
+            let path = format!("outputs.{index}");
+            oids.push((
+                format!("{path}.remaining_capacity"),
+                Some(output.remaining_capacity.as_str()),
+            ));
+            oids.push((
+                format!("{path}.max_capacity"),
+                Some(output.max_capacity.as_str()),
+            ));
+            oids.push((format!("{path}.status"), output.status.as_deref()));
+        }
+
+        oids.into_iter()
+            .filter_map(|(path, oid)| Some((path, oid?)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        let error = serde_json::from_value::<ModelDefinition>(json!({
+            "toner": { "black": { "levle": "1.3.6.1" } }
+        }))
+        .unwrap_err();
+
+        assert!(error.to_string().contains("unknown field `levle`"));
+    }
+
+    #[test]
+    fn lists_every_oid_with_its_path() {
+        let model: ModelDefinition = serde_json::from_value(json!({
+            "match": { "sys_object_id": "1.3.6.1.4.1.253", "description": "AltaLink" },
+            "toner": { "cyan": { "level": "1.1", "max_level": "1.2" } },
+            "outputs": [{ "name": "Bin", "remaining_capacity": "2.1", "max_capacity": "2.2" }]
+        }))
+        .unwrap();
+
+        let oids = model.oids();
+        let paths: Vec<&str> = oids.iter().map(|(p, _)| p.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "match.sys_object_id",
+                "toner.cyan.level",
+                "toner.cyan.max_level",
+                "outputs.0.remaining_capacity",
+                "outputs.0.max_capacity"
+            ]
+        );
+        assert_eq!(
+            model.match_rules.unwrap().description.to_vec(),
+            vec!["AltaLink"]
+        );
+    }
+}
@@ -0,0 +1,327 @@
+use serde::Serialize;
+use std::fmt::Display;
+
+/// What the printer is currently doing (`hrPrinterStatus`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrinterStatus {
+    Other,
+    Unknown,
+    Idle,
+    Printing,
+    Warmup,
+}
+
+impl PrinterStatus {
+    /// Decodes an `hrPrinterStatus` value.
+    pub fn from_value(value: i64) -> Self {
+        match value {
+            1 => Self::Other,
+            3 => Self::Idle,
+            4 => Self::Printing,
+            5 => Self::Warmup,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Machine-readable name, as used in JSON, CSV and metric labels.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Other => "other",
+            Self::Unknown => "unknown",
+            Self::Idle => "idle",
+            Self::Printing => "printing",
+            Self::Warmup => "warmup",
+        }
+    }
+}
+
+impl Display for PrinterStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Other => write!(f, "Other"),
+            Self::Unknown => write!(f, "Unknown"),
+            Self::Idle => write!(f, "Idle"),
+            Self::Printing => write!(f, "Printing"),
+            Self::Warmup => write!(f, "Warming up"),
+        }
+    }
+}
+
+/// Overall health of the device (`hrDeviceStatus`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeviceStatus {
+    Unknown,
+    Running,
+    Warning,
+    Testing,
+    Down,
+}
+
+impl DeviceStatus {
+    /// Decodes an `hrDeviceStatus` value.
+    pub fn from_value(value: i64) -> Self {
+        match value {
+            2 => Self::Running,
+            3 => Self::Warning,
+            4 => Self::Testing,
+            5 => Self::Down,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Machine-readable name, as used in JSON, CSV and metric labels.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Unknown => "unknown",
+            Self::Running => "running",
+            Self::Warning => "warning",
+            Self::Testing => "testing",
+            Self::Down => "down",
+        }
+    }
+}
+
+impl Display for DeviceStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unknown => write!(f, "Unknown"),
+            Self::Running => write!(f, "Running"),
+            Self::Warning => write!(f, "Warning"),
+            Self::Testing => write!(f, "Testing"),
+            Self::Down => write!(f, "Down"),
+        }
+    }
+}
+
+/// A condition flagged in `hrPrinterDetectedErrorState`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrinterError {
+    LowPaper,
+    NoPaper,
+    LowToner,
+    NoToner,
+    DoorOpen,
+    Jammed,
+    Offline,
+    ServiceRequested,
+    InputTrayMissing,
+    OutputTrayMissing,
+    MarkerSupplyMissing,
+    OutputNearFull,
+    OutputFull,
+    InputTrayEmpty,
+    OverduePreventMaint,
+}
+
+/// The error flags in bit order: bit 0 is the most significant bit of the first octet.
+const ERROR_BITS: [PrinterError; 15] = [
+    PrinterError::LowPaper,
+    PrinterError::NoPaper,
+    PrinterError::LowToner,
+    PrinterError::NoToner,
+    PrinterError::DoorOpen,
+    PrinterError::Jammed,
+    PrinterError::Offline,
+    PrinterError::ServiceRequested,
+    PrinterError::InputTrayMissing,
+    PrinterError::OutputTrayMissing,
+    PrinterError::MarkerSupplyMissing,
+    PrinterError::OutputNearFull,
+    PrinterError::OutputFull,
+    PrinterError::InputTrayEmpty,
+    PrinterError::OverduePreventMaint,
+];
+
+impl PrinterError {
+    /// Decodes the `hrPrinterDetectedErrorState` bitmask into the flags that are set.
+    ///
+    /// Bits beyond the known flags are ignored, as are missing trailing octets.
+    pub fn decode(bitmask: &[u8]) -> Vec<Self> {
+        ERROR_BITS
+            .into_iter()
+            .enumerate()
+            .filter(|(bit, _)| {
+                bitmask
+                    .get(bit / 8)
+                    .is_some_and(|octet| octet & (0x80 >> (bit % 8)) != 0)
+            })
+            .map(|(_, error)| error)
+            .collect()
+    }
+
+    /// Machine-readable name, as used in JSON, CSV and metric labels.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::LowPaper => "low_paper",
+            Self::NoPaper => "no_paper",
+            Self::LowToner => "low_toner",
+            Self::NoToner => "no_toner",
+            Self::DoorOpen => "door_open",
+            Self::Jammed => "jammed",
+            Self::Offline => "offline",
+            Self::ServiceRequested => "service_requested",
+            Self::InputTrayMissing => "input_tray_missing",
+            Self::OutputTrayMissing => "output_tray_missing",
+            Self::MarkerSupplyMissing => "marker_supply_missing",
+            Self::OutputNearFull => "output_near_full",
+            Self::OutputFull => "output_full",
+            Self::InputTrayEmpty => "input_tray_empty",
+            Self::OverduePreventMaint => "overdue_prevent_maint",
+        }
+    }
+}
+
+impl Display for PrinterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::LowPaper => write!(f, "Low paper"),
+            Self::NoPaper => write!(f, "No paper"),
+            Self::LowToner => write!(f, "Low toner"),
+            Self::NoToner => write!(f, "No toner"),
+            Self::DoorOpen => write!(f, "Door open"),
+            Self::Jammed => write!(f, "Jammed"),
+            Self::Offline => write!(f, "Offline"),
+            Self::ServiceRequested => write!(f, "Service requested"),
+            Self::InputTrayMissing => write!(f, "Input tray missing"),
+            Self::OutputTrayMissing => write!(f, "Output tray missing"),
+            Self::MarkerSupplyMissing => write!(f, "Supply missing"),
+            Self::OutputNearFull => write!(f, "Output almost full"),
+            Self::OutputFull => write!(f, "Output full"),
+            Self::InputTrayEmpty => write!(f, "Input tray empty"),
+            Self::OverduePreventMaint => write!(f, "Maintenance overdue"),
+        }
+    }
+}
+
+/// Availability part of a `PrtSubUnitStatusTC` value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Availability {
+    Idle,
+    Standby,
+    Active,
+    Busy,
+    UnavailableOnRequest,
+    Broken,
+    Unknown,
+}
+
+impl Display for Availability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Idle => write!(f, "Ready"),
+            Self::Standby => write!(f, "Standby"),
+            Self::Active => write!(f, "Active"),
+            Self::Busy => write!(f, "Busy"),
+            Self::UnavailableOnRequest => write!(f, "Unavailable"),
+            Self::Broken => write!(f, "Broken"),
+            Self::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+/// Status of a printer sub-unit such as an input tray (`PrtSubUnitStatusTC`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SubunitStatus {
+    pub availability: Availability,
+    pub warning: bool,
+    pub critical: bool,
+    pub offline: bool,
+    pub transitioning: bool,
+}
+
+impl SubunitStatus {
+    /// Decodes a `PrtSubUnitStatusTC` value: the availability in the low three bits,
+    /// followed by the alert, off-line and transitioning flags.
+    pub fn from_value(value: i64) -> Self {
+        let availability = match value & 0b111 {
+            0 => Availability::Idle,
+            1 => Availability::UnavailableOnRequest,
+            2 => Availability::Standby,
+            3 => Availability::Broken,
+            4 => Availability::Active,
+            6 => Availability::Busy,
+            _ => Availability::Unknown,
+        };
+
+        Self {
+            availability,
+            warning: value & 8 != 0,
+            critical: value & 16 != 0,
+            offline: value & 32 != 0,
+            transitioning: value & 64 != 0,
+        }
+    }
+}
+
+impl Display for SubunitStatus {
+    /// Formats the status as e.g. `Ready` or `Unavailable, Offline, Critical alert`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.availability)?;
+
+        for (set, text) in [
+            (self.offline, "Offline"),
+            (self.critical, "Critical alert"),
+            (self.warning, "Warning"),
+            (self.transitioning, "Transitioning"),
+        ] {
+            if set {
+                write!(f, ", {text}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_status_values() {
+        assert_eq!(PrinterStatus::from_value(3), PrinterStatus::Idle);
+        assert_eq!(PrinterStatus::from_value(0), PrinterStatus::Unknown);
+        assert_eq!(DeviceStatus::from_value(5), DeviceStatus::Down);
+        assert_eq!(DeviceStatus::Warning.name(), "warning");
+    }
+
+    #[test]
+    fn decodes_error_bits_most_significant_first() {
+        assert_eq!(
+            PrinterError::decode(&[0b0000_1100]),
+            vec![PrinterError::DoorOpen, PrinterError::Jammed]
+        );
+        assert_eq!(
+            PrinterError::decode(&[0b0000_0001, 0b0010_0000]),
+            vec![
+                PrinterError::ServiceRequested,
+                PrinterError::MarkerSupplyMissing
+            ]
+        );
+    }
+
+    #[test]
+    fn decodes_subunit_status() {
+        assert_eq!(SubunitStatus::from_value(0).to_string(), "Ready");
+        assert_eq!(
+            SubunitStatus::from_value(1 | 16 | 32).to_string(),
+            "Unavailable, Offline, Critical alert"
+        );
+        assert_eq!(
+            SubunitStatus::from_value(5).availability,
+            Availability::Unknown
+        );
+    }
+
+    #[test]
+    fn empty_or_clear_bitmask_has_no_errors() {
+        assert!(PrinterError::decode(&[]).is_empty());
+        assert!(PrinterError::decode(&[0, 0]).is_empty());
+        // The last bit of the second octet is not assigned
+        assert!(PrinterError::decode(&[0, 0b0000_0001]).is_empty());
+    }
+}
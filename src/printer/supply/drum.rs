@@ -1,4 +1,4 @@
-use crate::printer::supply::CalculateLevel;
+use crate::printer::supply::{CalculateLevel, SupplyState};
 use serde::Serialize;
 
 /// Represents a single imaging drum unit, tracking its current usage and maximum capacity.
@@ -7,15 +7,19 @@ pub struct Drum {
     pub level: i64,
     pub max_level: i64,
     pub level_percent: Option<i64>,
+    pub state: SupplyState,
 }
 
 impl Drum {
     /// Creates a new [`Drum`] instance.
+    ///
+    /// A known `level_percent` makes the state [`SupplyState::Exact`].
     pub fn new(level: i64, max_level: i64, level_percent: Option<i64>) -> Self {
         Self {
             level,
             max_level,
             level_percent,
+            state: level_percent.map_or(SupplyState::Unknown, SupplyState::Exact),
         }
     }
 }
@@ -34,15 +38,12 @@ pub struct Drums {
 impl CalculateLevel for Option<Drum> {
     /// Computes the remaining life percentage based on current and maximum levels.
     ///
-    /// Updates `level_percent` only if the drum exists and `max_level` is greater than zero
-    /// to avoid division by zero errors.
+    /// Interprets the levels as a [`SupplyState`], honouring the Printer-MIB special values,
+    /// and sets `level_percent` only when the remaining percentage is known.
     fn calculate_level_percent(&mut self) {
         if let Some(drum) = self {
-            if drum.max_level > 0 {
-                drum.level_percent = Some((drum.level * 100) / drum.max_level);
-            } else {
-                drum.level_percent = None;
-            }
+            drum.state = SupplyState::from_levels(drum.level, drum.max_level);
+            drum.level_percent = drum.state.percent();
         }
     }
 }
@@ -1,4 +1,4 @@
-use crate::printer::supply::CalculateLevel;
+use crate::printer::supply::{CalculateLevel, SupplyState};
 use serde::Serialize;
 
 /// Represents the printer's fuser unit, responsible for bonding toner to paper.
@@ -9,15 +9,19 @@ pub struct Fuser {
     pub level: i64,
     pub max_level: i64,
     pub level_percent: Option<i64>,
+    pub state: SupplyState,
 }
 
 impl Fuser {
     /// Creates a new [`Fuser`] instance.
+    ///
+    /// A known `level_percent` makes the state [`SupplyState::Exact`].
     pub fn new(level: i64, max_level: i64, level_percent: Option<i64>) -> Self {
         Self {
             level,
             max_level,
             level_percent,
+            state: level_percent.map_or(SupplyState::Unknown, SupplyState::Exact),
         }
     }
 }
@@ -25,14 +29,12 @@ impl Fuser {
 impl CalculateLevel for Option<Fuser> {
     /// Computes the remaining life percentage based on current and maximum levels.
     ///
-    /// Updates `level_percent` only if the fuser exists and `max_level` is valid (greater than zero).
+    /// Interprets the levels as a [`SupplyState`] and sets `level_percent` only when the
+    /// remaining percentage is known.
     fn calculate_level_percent(&mut self) {
         if let Some(fuser) = self {
-            if fuser.max_level > 0 {
-                fuser.level_percent = Some((fuser.level * 100) / fuser.max_level);
-            } else {
-                fuser.level_percent = None;
-            }
+            fuser.state = SupplyState::from_levels(fuser.level, fuser.max_level);
+            fuser.level_percent = fuser.state.percent();
         }
     }
 }
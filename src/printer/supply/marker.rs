@@ -0,0 +1,154 @@
+use super::PrinterSupply;
+use serde::{Serialize, Serializer};
+use std::fmt::Display;
+
+// Generates the supply type enum with its raw value, machine-readable name and label,
+// so the long list of values is written only once.
+macro_rules! supply_types {
+    ($($variant:ident = $value:literal, $name:literal, $text:literal;)*) => {
+        /// The type of a supply (`PrtMarkerSuppliesTypeTC`, RFC 3805 and IANA).
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum SupplyType {
+            $($variant,)*
+        }
+
+        impl SupplyType {
+            /// Decodes a `prtMarkerSuppliesType` value. Unassigned values are `Unknown`.
+            pub fn from_value(value: i64) -> Self {
+                match value {
+                    $($value => Self::$variant,)*
+                    _ => Self::Unknown,
+                }
+            }
+
+            /// Machine-readable name, as used in JSON output.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $name,)*
+                }
+            }
+        }
+
+        impl Display for SupplyType {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    $(Self::$variant => write!(f, $text),)*
+                }
+            }
+        }
+    };
+}
+
+supply_types! {
+    Other = 1, "other", "Other";
+    Unknown = 2, "unknown", "Unknown";
+    Toner = 3, "toner", "Toner";
+    WasteToner = 4, "waste_toner", "Waste Toner";
+    Ink = 5, "ink", "Ink";
+    InkCartridge = 6, "ink_cartridge", "Ink Cartridge";
+    InkRibbon = 7, "ink_ribbon", "Ink Ribbon";
+    WasteInk = 8, "waste_ink", "Waste Ink";
+    Opc = 9, "opc", "Drum";
+    Developer = 10, "developer", "Developer";
+    FuserOil = 11, "fuser_oil", "Fuser Oil";
+    SolidWax = 12, "solid_wax", "Solid Wax";
+    RibbonWax = 13, "ribbon_wax", "Ribbon Wax";
+    WasteWax = 14, "waste_wax", "Waste Wax";
+    Fuser = 15, "fuser", "Fuser";
+    CoronaWire = 16, "corona_wire", "Corona Wire";
+    FuserOilWick = 17, "fuser_oil_wick", "Fuser Oil Wick";
+    CleanerUnit = 18, "cleaner_unit", "Cleaner Unit";
+    FuserCleaningPad = 19, "fuser_cleaning_pad", "Fuser Cleaning Pad";
+    TransferUnit = 20, "transfer_unit", "Transfer Unit";
+    TonerCartridge = 21, "toner_cartridge", "Toner Cartridge";
+    FuserOiler = 22, "fuser_oiler", "Fuser Oiler";
+    Water = 23, "water", "Water";
+    WasteWater = 24, "waste_water", "Waste Water";
+    GlueWaterAdditive = 25, "glue_water_additive", "Glue Water Additive";
+    WastePaper = 26, "waste_paper", "Waste Paper";
+    BindingSupply = 27, "binding_supply", "Binding Supply";
+    BandingSupply = 28, "banding_supply", "Banding Supply";
+    StitchingWire = 29, "stitching_wire", "Stitching Wire";
+    ShrinkWrap = 30, "shrink_wrap", "Shrink Wrap";
+    PaperWrap = 31, "paper_wrap", "Paper Wrap";
+    Staples = 32, "staples", "Staples";
+    Inserts = 33, "inserts", "Inserts";
+    Covers = 34, "covers", "Covers";
+    MatteToner = 35, "matte_toner", "Matte Toner";
+    MatteInk = 36, "matte_ink", "Matte Ink";
+}
+
+impl SupplyType {
+    /// The type assumed for supplies mapped without one, e.g. by a vendor driver.
+    pub fn from_kind(kind: PrinterSupply) -> Self {
+        match kind {
+            PrinterSupply::Toner => Self::Toner,
+            PrinterSupply::Drum => Self::Opc,
+            PrinterSupply::Fuser => Self::Fuser,
+            PrinterSupply::Reservoir => Self::WasteToner,
+            PrinterSupply::Other => Self::Other,
+        }
+    }
+}
+
+impl Serialize for SupplyType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+/// Whether a supply is used up or fills up over time (`PrtMarkerSuppliesClassTC`).
+///
+/// For a receptacle, such as a waste toner box, the reported level is the space left,
+/// so in both cases a low level needs attention.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SupplyClass {
+    Other,
+    /// A supply that is used up, e.g. a toner cartridge.
+    Consumed,
+    /// A container that fills up, e.g. a waste toner box.
+    Receptacle,
+}
+
+impl SupplyClass {
+    /// Decodes a `prtMarkerSuppliesClass` value.
+    pub fn from_value(value: i64) -> Self {
+        match value {
+            3 => Self::Consumed,
+            4 => Self::Receptacle,
+            _ => Self::Other,
+        }
+    }
+
+    /// The class assumed for supplies mapped without one, e.g. by a vendor driver.
+    pub fn from_kind(kind: PrinterSupply) -> Self {
+        match kind {
+            PrinterSupply::Reservoir => Self::Receptacle,
+            _ => Self::Consumed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_supply_types() {
+        assert_eq!(SupplyType::from_value(20), SupplyType::TransferUnit);
+        assert_eq!(SupplyType::from_value(32).name(), "staples");
+        assert_eq!(SupplyType::from_value(28).to_string(), "Banding Supply");
+        assert_eq!(SupplyType::from_value(999), SupplyType::Unknown);
+    }
+
+    #[test]
+    fn decodes_supply_classes() {
+        assert_eq!(SupplyClass::from_value(3), SupplyClass::Consumed);
+        assert_eq!(SupplyClass::from_value(4), SupplyClass::Receptacle);
+        assert_eq!(
+            SupplyClass::from_kind(PrinterSupply::Reservoir),
+            SupplyClass::Receptacle
+        );
+    }
+}
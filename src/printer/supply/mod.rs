@@ -1,14 +1,114 @@
+use marker::{SupplyClass, SupplyType};
+use serde::{Serialize, Serializer};
+use std::fmt::Display;
+
 pub mod drum;
 pub mod fuser;
+pub mod marker;
 pub mod reservoir;
 pub mod toner;
 
+/// Level value meaning the supply has no restriction on its level (RFC 3805 `other`).
+const LEVEL_OTHER: i64 = -1;
+/// Level value meaning the level is unknown.
+const LEVEL_UNKNOWN: i64 = -2;
+/// Level value meaning some of the supply remains, without a known amount.
+const LEVEL_SOME_REMAINING: i64 = -3;
+
+/// The remaining amount of a supply, as far as the printer reports it.
+///
+/// Printer-MIB (RFC 3805) reserves negative values of `prtMarkerSuppliesLevel` and
+/// `prtMarkerSuppliesMaxCapacity` for states that cannot be turned into a percentage.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SupplyState {
+    /// The remaining percentage is known.
+    Exact(i64),
+    /// Some of the supply remains, but the printer does not report how much.
+    SomeRemaining,
+    /// The printer does not report the level.
+    #[default]
+    Unknown,
+    /// The supply is not restricted, e.g. a tank connected to a continuous supply.
+    Unlimited,
+}
+
+impl SupplyState {
+    /// Interprets a raw level and maximum capacity.
+    pub fn from_levels(level: i64, max_level: i64) -> Self {
+        match (level, max_level) {
+            (LEVEL_SOME_REMAINING, _) => Self::SomeRemaining,
+            (LEVEL_UNKNOWN, _) => Self::Unknown,
+            (LEVEL_OTHER, _) | (_, LEVEL_OTHER) => Self::Unlimited,
+            (level, max_level) if level >= 0 && max_level > 0 => {
+                Self::Exact(((level * 100) / max_level).min(100))
+            }
+            _ => Self::Unknown,
+        }
+    }
+
+    /// The raw level Printer-MIB reserves for a state, by its [`name`](Self::name), e.g.
+    /// `-3` for `some_remaining`. `exact` has no reserved level.
+    pub fn reserved_level(name: &str) -> Option<i64> {
+        match name {
+            "some_remaining" => Some(LEVEL_SOME_REMAINING),
+            "unknown" => Some(LEVEL_UNKNOWN),
+            "unlimited" => Some(LEVEL_OTHER),
+            _ => None,
+        }
+    }
+
+    /// Whether a raw level is one of the values Printer-MIB reserves for a state.
+    pub fn is_reserved_level(level: i64) -> bool {
+        matches!(level, LEVEL_OTHER | LEVEL_UNKNOWN | LEVEL_SOME_REMAINING)
+    }
+
+    /// The remaining percentage, when it is known.
+    pub fn percent(&self) -> Option<i64> {
+        match self {
+            Self::Exact(percent) => Some(*percent),
+            _ => None,
+        }
+    }
+
+    /// Machine-readable name of the state, as used in JSON and CSV output.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Exact(_) => "exact",
+            Self::SomeRemaining => "some_remaining",
+            Self::Unknown => "unknown",
+            Self::Unlimited => "unlimited",
+        }
+    }
+}
+
+impl Display for SupplyState {
+    /// Formats the state for display: the percentage, `OK` when some supply is known to
+    /// remain, or `?` when the level is unknown.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Exact(percent) => write!(f, "{percent}%"),
+            Self::SomeRemaining | Self::Unlimited => write!(f, "OK"),
+            Self::Unknown => write!(f, "?"),
+        }
+    }
+}
+
+impl Serialize for SupplyState {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
 /// Categorizes the different types of replaceable printer consumables.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum PrinterSupply {
     Toner,
     Drum,
     Fuser,
     Reservoir,
+    /// Any other supply, e.g. a transfer unit or staples; see [`Supply::supply_type`].
+    Other,
 }
 
 impl std::fmt::Display for PrinterSupply {
@@ -19,6 +119,7 @@ impl std::fmt::Display for PrinterSupply {
             Self::Drum => write!(f, "Drum"),
             Self::Fuser => write!(f, "Fuser"),
             Self::Reservoir => write!(f, "Reservoir"),
+            Self::Other => write!(f, "Other"),
         }
     }
 }
@@ -29,3 +130,195 @@ impl std::fmt::Display for PrinterSupply {
 pub trait CalculateLevel {
     fn calculate_level_percent(&mut self);
 }
+
+/// A single consumable reported by the printer.
+///
+/// Unlike the fixed CMYK slots of [`toner::Toners`] and [`drum::Drums`], any number of
+/// supplies in any colour can be described, e.g. light cyan ink or a white toner.
+#[derive(Debug, Clone, Serialize)]
+pub struct Supply {
+    pub kind: PrinterSupply,
+    /// Printer-MIB supply type, e.g. `waste_toner` or `transfer_unit`.
+    pub supply_type: SupplyType,
+    /// Whether the supply is consumed or is a receptacle that fills up.
+    pub class: SupplyClass,
+    /// Colour name, e.g. `Cyan` or `Photo Black`.
+    pub color: Option<String>,
+    /// Colorant value as reported by the printer (`prtMarkerColorantValue`).
+    pub colorant: Option<String>,
+    /// Description as reported by the printer.
+    pub description: Option<String>,
+    pub level: i64,
+    pub max_level: i64,
+    pub level_percent: Option<i64>,
+    pub state: SupplyState,
+}
+
+impl Supply {
+    /// Creates a new [`Supply`] instance.
+    ///
+    /// A known `level_percent` makes the state [`SupplyState::Exact`]. The type and class
+    /// are derived from `kind` until set with [`Supply::with_type`].
+    pub fn new(
+        kind: PrinterSupply,
+        level: i64,
+        max_level: i64,
+        level_percent: Option<i64>,
+    ) -> Self {
+        Self {
+            kind,
+            supply_type: SupplyType::from_kind(kind),
+            class: SupplyClass::from_kind(kind),
+            color: None,
+            colorant: None,
+            description: None,
+            level,
+            max_level,
+            level_percent,
+            state: level_percent.map_or(SupplyState::Unknown, SupplyState::Exact),
+        }
+    }
+
+    /// Sets the Printer-MIB type and class.
+    pub fn with_type(mut self, supply_type: SupplyType, class: SupplyClass) -> Self {
+        self.supply_type = supply_type;
+        self.class = class;
+        self
+    }
+
+    /// Sets the colour name.
+    pub fn with_color(mut self, color: impl Into<String>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Sets the colorant value as reported by the printer.
+    pub fn with_colorant(mut self, colorant: impl Into<String>) -> Self {
+        self.colorant = Some(colorant.into());
+        self
+    }
+
+    /// Sets the description.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Human-readable name of the supply, e.g. `Cyan Toner`, `Light Cyan Ink` or
+    /// `Transfer Unit`.
+    pub fn label(&self) -> String {
+        let name = match self.kind {
+            PrinterSupply::Other => self.supply_type.to_string(),
+            PrinterSupply::Toner if self.is_ink() => "Ink".to_string(),
+            kind => kind.to_string(),
+        };
+
+        match &self.color {
+            Some(color) => format!("{color} {name}"),
+            None => name,
+        }
+    }
+
+    /// Whether the supply is ink. Ink is judged and listed with the toners, but named
+    /// as ink.
+    pub fn is_ink(&self) -> bool {
+        matches!(
+            self.supply_type,
+            SupplyType::Ink | SupplyType::InkCartridge | SupplyType::MatteInk
+        )
+    }
+
+    /// Whether the supply fills up, so its level is the space left rather than the
+    /// amount remaining.
+    pub fn is_receptacle(&self) -> bool {
+        self.class == SupplyClass::Receptacle
+    }
+
+    /// Whether the colour is the given one, ignoring case.
+    pub fn is_color(&self, color: &str) -> bool {
+        self.color
+            .as_deref()
+            .is_some_and(|c| c.eq_ignore_ascii_case(color))
+    }
+}
+
+impl CalculateLevel for Supply {
+    /// Interprets the levels as a [`SupplyState`] and sets `level_percent` only when the
+    /// remaining percentage is known.
+    fn calculate_level_percent(&mut self) {
+        self.state = SupplyState::from_levels(self.level, self.max_level);
+        self.level_percent = self.state.percent();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PrinterSupply, Supply, SupplyState};
+    use crate::printer::supply::marker::{SupplyClass, SupplyType};
+
+    #[test]
+    fn exact_levels() {
+        assert_eq!(SupplyState::from_levels(40, 50), SupplyState::Exact(80));
+        assert_eq!(SupplyState::from_levels(0, 100), SupplyState::Exact(0));
+        assert_eq!(SupplyState::from_levels(120, 100), SupplyState::Exact(100));
+    }
+
+    #[test]
+    fn special_levels() {
+        assert_eq!(
+            SupplyState::from_levels(-3, 100),
+            SupplyState::SomeRemaining
+        );
+        assert_eq!(SupplyState::from_levels(-3, -2), SupplyState::SomeRemaining);
+        assert_eq!(SupplyState::from_levels(-2, 100), SupplyState::Unknown);
+        assert_eq!(SupplyState::from_levels(-1, 100), SupplyState::Unlimited);
+    }
+
+    #[test]
+    fn special_capacities() {
+        assert_eq!(SupplyState::from_levels(50, -1), SupplyState::Unlimited);
+        assert_eq!(SupplyState::from_levels(50, -2), SupplyState::Unknown);
+        assert_eq!(SupplyState::from_levels(50, 0), SupplyState::Unknown);
+    }
+
+    #[test]
+    fn display_and_name() {
+        assert_eq!(SupplyState::Exact(42).to_string(), "42%");
+        assert_eq!(SupplyState::SomeRemaining.to_string(), "OK");
+        assert_eq!(SupplyState::Unknown.to_string(), "?");
+        assert_eq!(SupplyState::Unlimited.name(), "unlimited");
+    }
+
+    #[test]
+    fn supply_label_and_color() {
+        let supply = Supply::new(PrinterSupply::Toner, 40, 50, None).with_color("Light Cyan");
+
+        assert_eq!(supply.label(), "Light Cyan Toner");
+        assert!(supply.is_color("light cyan"));
+        assert_eq!(supply.state, SupplyState::Unknown);
+        assert_eq!(
+            Supply::new(PrinterSupply::Fuser, 0, 0, Some(70)).state,
+            SupplyState::Exact(70)
+        );
+    }
+
+    #[test]
+    fn ink_is_labelled_as_ink() {
+        let supply = Supply::new(PrinterSupply::Toner, 40, 50, None)
+            .with_type(SupplyType::InkCartridge, SupplyClass::Consumed)
+            .with_color("Light Cyan");
+
+        assert!(supply.is_ink());
+        assert_eq!(supply.label(), "Light Cyan Ink");
+    }
+
+    #[test]
+    fn other_supplies_are_labelled_by_type() {
+        let supply = Supply::new(PrinterSupply::Other, 10, 100, None)
+            .with_type(SupplyType::TransferUnit, SupplyClass::Consumed);
+
+        assert_eq!(supply.label(), "Transfer Unit");
+        assert!(!supply.is_receptacle());
+        assert!(Supply::new(PrinterSupply::Reservoir, 10, 100, None).is_receptacle());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::printer::supply::CalculateLevel;
+use crate::printer::supply::{CalculateLevel, SupplyState};
 use serde::Serialize;
 
 /// Represents a waste toner container or ink reservoir.
@@ -10,15 +10,19 @@ pub struct Reservoir {
     pub level: i64,
     pub max_level: i64,
     pub level_percent: Option<i64>,
+    pub state: SupplyState,
 }
 
 impl Reservoir {
     /// Creates a new [`Reservoir`] instance.
+    ///
+    /// A known `level_percent` makes the state [`SupplyState::Exact`].
     pub fn new(level: i64, max_level: i64, level_percent: Option<i64>) -> Self {
         Self {
             level,
             max_level,
             level_percent,
+            state: level_percent.map_or(SupplyState::Unknown, SupplyState::Exact),
         }
     }
 }
@@ -26,14 +30,12 @@ impl Reservoir {
 impl CalculateLevel for Option<Reservoir> {
     /// Computes the fill percentage based on current and maximum levels.
     ///
-    /// Updates `level_percent` only if the reservoir exists and `max_level` is greater than zero.
+    /// Interprets the levels as a [`SupplyState`] and sets `level_percent` only when the
+    /// fill percentage is known.
     fn calculate_level_percent(&mut self) {
         if let Some(reservoir) = self {
-            if reservoir.max_level > 0 {
-                reservoir.level_percent = Some((reservoir.level * 100) / reservoir.max_level);
-            } else {
-                reservoir.level_percent = None;
-            }
+            reservoir.state = SupplyState::from_levels(reservoir.level, reservoir.max_level);
+            reservoir.level_percent = reservoir.state.percent();
         }
     }
 }
@@ -1,4 +1,4 @@
-use crate::printer::supply::CalculateLevel;
+use crate::printer::supply::{CalculateLevel, SupplyState};
 use serde::Serialize;
 use std::fmt::{Display, Formatter};
 
@@ -19,15 +19,19 @@ pub struct Toner {
     pub level: i64,
     pub max_level: i64,
     pub level_percent: Option<i64>,
+    pub state: SupplyState,
 }
 
 impl Toner {
     /// Creates a new [`Toner`] instance.
+    ///
+    /// A known `level_percent` makes the state [`SupplyState::Exact`].
     pub fn new(level: i64, max_level: i64, level_percent: Option<i64>) -> Self {
         Self {
             level,
             max_level,
             level_percent,
+            state: level_percent.map_or(SupplyState::Unknown, SupplyState::Exact),
         }
     }
 }
@@ -35,19 +39,19 @@ impl Toner {
 impl CalculateLevel for Option<Toner> {
     /// Computes the remaining life percentage based on current and maximum levels.
     ///
-    /// Updates `level_percent` only if the toner exists and `max_level` is greater than zero.
+    /// Interprets the levels as a [`SupplyState`], honouring the Printer-MIB special values
+    /// (`-1` other, `-2` unknown, `-3` some remaining), and sets `level_percent` only when
+    /// the remaining percentage is known.
     fn calculate_level_percent(&mut self) {
         if let Some(toner) = self {
-            if toner.max_level > 0 {
-                toner.level_percent = Some((toner.level * 100) / toner.max_level);
-            } else {
-                toner.level_percent = None;
-            }
+            toner.state = SupplyState::from_levels(toner.level, toner.max_level);
+            toner.level_percent = toner.state.percent();
         }
     }
 }
 
 /// Enumerates the standard CMYK color model used for printer toner cartridges.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TonerColor {
     Black,
     Cyan,
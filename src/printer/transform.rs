@@ -0,0 +1,383 @@
+use crate::{
+    error::{AppError, ErrorKind},
+    printer::supply::SupplyState,
+    snmp::value::SnmpValues,
+    utils::parse_oid_to_vec,
+};
+use regex::Regex;
+use serde::{
+    Deserialize, Deserializer,
+    de::{Error, Unexpected},
+};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// A value of a data file entry, such as a supply's `level` or a page counter.
+///
+/// It is either an OID read as is, a constant (e.g. a fixed `max_level` of `100`), or
+/// a [`Transform`] of one or more OIDs.
+#[derive(Debug, Clone)]
+pub enum ValueDefinition {
+    Oid(String),
+    Constant(i64),
+    Transform(Box<Transform>),
+}
+
+impl<'de> Deserialize<'de> for ValueDefinition {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Value::deserialize(deserializer)? {
+            Value::String(oid) => Ok(Self::Oid(oid)),
+            Value::Number(number) => number
+                .as_i64()
+                .map(Self::Constant)
+                .ok_or_else(|| D::Error::custom(format!("expected an integer, found {number}"))),
+            transform @ Value::Object(_) => serde_json::from_value(transform)
+                .map(|t| Self::Transform(Box::new(t)))
+                .map_err(D::Error::custom),
+            _ => Err(D::Error::invalid_type(
+                Unexpected::Other("value"),
+                &"an OID, an integer or a transform object",
+            )),
+        }
+    }
+}
+
+impl ValueDefinition {
+    /// Lists the OIDs the value is read from, with the path suffix of each, e.g.
+    /// `.sum.1`. A plain OID has an empty suffix.
+    pub fn oids(&self) -> Vec<(String, &str)> {
+        match self {
+            Self::Oid(oid) => vec![(String::new(), oid.as_str())],
+            Self::Constant(_) => Vec::new(),
+            Self::Transform(transform) => {
+                let (key, oids) = match &transform.input {
+                    Input::Oid(oid) => return vec![(".oid".to_string(), oid.as_str())],
+                    Input::Sum(oids) => ("sum", oids),
+                    Input::Difference(oids) => ("difference", oids),
+                };
+
+                oids.iter()
+                    .enumerate()
+                    .map(|(index, oid)| (format!(".{key}.{index}"), oid.as_str()))
+                    .collect()
+            }
+        }
+    }
+}
+
+/// The OIDs a [`Transform`] reads.
+#[derive(Debug, Clone)]
+pub enum Input {
+    Oid(String),
+    /// Values added up, e.g. the full colour, twin colour and mono colour counters.
+    Sum(Vec<String>),
+    /// The first value minus the others.
+    Difference(Vec<String>),
+}
+
+/// Turns the raw values of one or more OIDs into a level or a counter.
+///
+/// Steps run in this order: `extract` and `map` on each raw value, then `sum` or
+/// `difference`, then `multiplier`, `divisor` and `offset`. The reserved levels `-1`,
+/// `-2` and `-3` skip the arithmetic and are kept as they are; any other result is at
+/// least 0, so it is never mistaken for one of them.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "TransformFields")]
+pub struct Transform {
+    pub input: Input,
+    /// Regular expression applied to the raw text; its first group (or the whole match)
+    /// is the value, e.g. `(\d+)%` for `75%`. Text it does not match is kept whole.
+    pub extract: Option<Regex>,
+    /// Raw values translated to a level, e.g. `{"ok": "some_remaining", "empty": 0}`.
+    pub map: BTreeMap<String, i64>,
+    pub multiplier: f64,
+    pub divisor: f64,
+    pub offset: f64,
+}
+
+/// The JSON form of a [`Transform`], before it is checked.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TransformFields {
+    oid: Option<String>,
+    sum: Option<Vec<String>>,
+    difference: Option<Vec<String>>,
+    extract: Option<String>,
+    #[serde(default)]
+    map: BTreeMap<String, MapTarget>,
+    multiplier: Option<f64>,
+    divisor: Option<f64>,
+    offset: Option<f64>,
+}
+
+/// A `map` target: a level, or the name of a supply state.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum MapTarget {
+    Level(i64),
+    State(String),
+}
+
+impl TryFrom<TransformFields> for Transform {
+    type Error = String;
+
+    fn try_from(fields: TransformFields) -> Result<Self, Self::Error> {
+        let input = match (fields.oid, fields.sum, fields.difference) {
+            (Some(oid), None, None) => Input::Oid(oid),
+            (None, Some(oids), None) if !oids.is_empty() => Input::Sum(oids),
+            (None, None, Some(oids)) if oids.len() >= 2 => Input::Difference(oids),
+            (None, None, Some(_)) => return Err("`difference` needs at least two OIDs".into()),
+            _ => return Err("expected exactly one of `oid`, `sum` or `difference`".into()),
+        };
+
+        let extract = fields
+            .extract
+            .map(|pattern| Regex::new(&pattern))
+            .transpose()
+            .map_err(|e| format!("invalid `extract` expression: {e}"))?;
+
+        let map = fields
+            .map
+            .into_iter()
+            .map(|(raw, target)| match target {
+                MapTarget::Level(level) => Ok((raw, level)),
+                MapTarget::State(name) => SupplyState::reserved_level(&name)
+                    .map(|level| (raw, level))
+                    .ok_or_else(|| {
+                        format!(
+                            "unknown state '{name}' in `map`, expected `some_remaining`, `unknown` or `unlimited`"
+                        )
+                    }),
+            })
+            .collect::<Result<_, _>>()?;
+
+        let divisor = fields.divisor.unwrap_or(1.0);
+        if divisor == 0.0 {
+            return Err("`divisor` must not be 0".into());
+        }
+
+        Ok(Self {
+            input,
+            extract,
+            map,
+            multiplier: fields.multiplier.unwrap_or(1.0),
+            divisor,
+            offset: fields.offset.unwrap_or(0.0),
+        })
+    }
+}
+
+impl Transform {
+    /// Applies `extract` and `map` to one raw value.
+    fn input_value(&self, text: &str) -> Result<f64, AppError> {
+        let text = text.trim_end_matches('\0').trim();
+
+        // Text the expression does not match is kept whole, so `map` can still handle it
+        let text = self
+            .extract
+            .as_ref()
+            .and_then(|regex| regex.captures(text))
+            .and_then(|captures| captures.get(1).or_else(|| captures.get(0)))
+            .map_or(text, |m| m.as_str());
+
+        if let Some(level) = self.map.get(text) {
+            return Ok(*level as f64);
+        }
+
+        text.parse::<f64>().map_err(|_| {
+            AppError::new(ErrorKind::TypeMismatch(format!(
+                "Expected numeric string, but got: '{text}'"
+            )))
+        })
+    }
+
+    /// Combines the input values and scales the result.
+    fn apply(&self, inputs: &[f64]) -> i64 {
+        if let Some(reserved) = inputs
+            .iter()
+            .find(|value| value.fract() == 0.0 && SupplyState::is_reserved_level(**value as i64))
+        {
+            return *reserved as i64;
+        }
+
+        let value = match &self.input {
+            Input::Oid(_) | Input::Sum(_) => inputs.iter().sum(),
+            Input::Difference(_) => inputs[0] - inputs[1..].iter().sum::<f64>(),
+        };
+
+        (value * self.multiplier / self.divisor + self.offset)
+            .round()
+            .max(0.0) as i64
+    }
+}
+
+/// A [`ValueDefinition`] with its OIDs parsed, ready to be read from fetched values.
+#[derive(Debug, Clone)]
+pub struct ValueReader {
+    oids: Vec<Vec<u64>>,
+    definition: ValueDefinition,
+}
+
+impl ValueReader {
+    /// Parses the OIDs of a definition. A plain empty OID gives [`None`], like a missing one.
+    pub fn new(definition: &ValueDefinition) -> Result<Option<Self>, AppError> {
+        if matches!(definition, ValueDefinition::Oid(oid) if oid.is_empty()) {
+            return Ok(None);
+        }
+
+        let oids = definition
+            .oids()
+            .into_iter()
+            .map(|(_, oid)| parse_oid_to_vec(oid))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Some(Self {
+            oids,
+            definition: definition.clone(),
+        }))
+    }
+
+    /// The OIDs to fetch.
+    pub fn oids(&self) -> &[Vec<u64>] {
+        &self.oids
+    }
+
+    /// Computes the value from the fetched values.
+    pub fn read(&self, values: &SnmpValues) -> Result<i64, AppError> {
+        match &self.definition {
+            ValueDefinition::Oid(_) => values.get(&self.oids[0]),
+            ValueDefinition::Constant(value) => Ok(*value),
+            ValueDefinition::Transform(transform) => {
+                let inputs = self
+                    .oids
+                    .iter()
+                    .map(|oid| {
+                        // Counters have no text form of their own
+                        let text = values
+                            .get::<String>(oid)
+                            .or_else(|_| values.get::<i64>(oid).map(|v| v.to_string()))?;
+                        transform.input_value(&text)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(transform.apply(&inputs))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn transform(value: Value) -> Transform {
+        match serde_json::from_value(value).unwrap() {
+            ValueDefinition::Transform(transform) => *transform,
+            other => panic!("expected a transform, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn reads_plain_oids_and_constants() {
+        assert!(matches!(
+            serde_json::from_value(json!("1.3.6.1")).unwrap(),
+            ValueDefinition::Oid(oid) if oid == "1.3.6.1"
+        ));
+        assert!(matches!(
+            serde_json::from_value(json!(100)).unwrap(),
+            ValueDefinition::Constant(100)
+        ));
+        assert!(
+            ValueReader::new(&ValueDefinition::Oid(String::new()))
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn scales_and_offsets_values() {
+        let tenths = transform(json!({ "oid": "1.1", "divisor": 10 }));
+        assert_eq!(tenths.apply(&[755.0]), 76);
+
+        let pages = transform(json!({ "oid": "1.1", "multiplier": 100, "divisor": 3000 }));
+        assert_eq!(pages.apply(&[1500.0]), 50);
+
+        let shifted = transform(json!({ "oid": "1.1", "offset": -1 }));
+        assert_eq!(shifted.apply(&[11.0]), 10);
+    }
+
+    #[test]
+    fn extracts_numbers_from_strings() {
+        let percent = transform(json!({
+            "oid": "1.1",
+            "extract": "(\\d+)%",
+            "map": { "OK": "some_remaining" }
+        }));
+
+        assert_eq!(percent.input_value("Toner 75%\0").unwrap(), 75.0);
+        assert_eq!(percent.input_value("OK").unwrap(), -3.0);
+        assert!(percent.input_value("n/a").is_err());
+    }
+
+    #[test]
+    fn maps_values_to_levels_and_states() {
+        let status = transform(json!({
+            "oid": "1.1",
+            "map": { "OK": "some_remaining", "EMPTY": 0 }
+        }));
+
+        assert_eq!(status.apply(&[status.input_value("OK").unwrap()]), -3);
+        assert_eq!(status.apply(&[status.input_value("EMPTY").unwrap()]), 0);
+    }
+
+    #[test]
+    fn combines_several_oids() {
+        let color = transform(json!({ "sum": ["1.1", "1.2", "1.3"] }));
+        assert_eq!(color.apply(&[100.0, 20.0, 3.0]), 123);
+
+        let mono = transform(json!({ "difference": ["1.1", "1.2"] }));
+        assert_eq!(mono.apply(&[500.0, 120.0]), 380);
+
+        // A reserved level is kept rather than added up
+        assert_eq!(color.apply(&[100.0, -2.0, 3.0]), -2);
+        assert_eq!(mono.apply(&[100.0, 120.0]), 0);
+    }
+
+    #[test]
+    fn only_reserved_levels_pass_through() {
+        let level = transform(json!({ "oid": "1.1", "offset": -1 }));
+
+        assert_eq!(level.apply(&[-3.0]), -3);
+        assert_eq!(level.apply(&[-5.0]), 0);
+        // 0 shifted by -1 must not turn into `unlimited`
+        assert_eq!(level.apply(&[0.0]), 0);
+    }
+
+    #[test]
+    fn lists_oids_with_their_path() {
+        let sum: ValueDefinition =
+            serde_json::from_value(json!({ "sum": ["1.1", "1.2"] })).unwrap();
+
+        assert_eq!(
+            sum.oids(),
+            vec![(".sum.0".to_string(), "1.1"), (".sum.1".to_string(), "1.2")]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_transforms() {
+        let error = |value: Value| {
+            serde_json::from_value::<ValueDefinition>(value)
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert!(error(json!({ "oid": "1.1", "sum": ["1.2"] })).contains("exactly one"));
+        assert!(error(json!({ "oid": "1.1", "divisor": 0 })).contains("must not be 0"));
+        assert!(error(json!({ "oid": "1.1", "extract": "(" })).contains("invalid `extract`"));
+        assert!(error(json!({ "oid": "1.1", "map": { "x": "low" } })).contains("unknown state"));
+        assert!(error(json!({ "oid": "1.1", "scale": 2 })).contains("unknown field `scale`"));
+        assert!(error(json!(1.5)).contains("expected an integer"));
+    }
+}
@@ -0,0 +1,123 @@
+use crate::printer::{status::SubunitStatus, supply::SupplyState};
+use serde::Serialize;
+
+/// `prtInputDimUnit` values: the unit of the declared media dimensions.
+const DIM_UNIT_TEN_THOUSANDTHS_OF_INCHES: i64 = 3;
+const DIM_UNIT_MICROMETERS: i64 = 4;
+
+/// Common media sizes in millimetres (short edge, long edge).
+const MEDIA_SIZES: &[(&str, i64, i64)] = &[
+    ("A3", 297, 420),
+    ("A4", 210, 297),
+    ("A5", 148, 210),
+    ("A6", 105, 148),
+    ("B4", 250, 353),
+    ("B5", 176, 250),
+    ("Letter", 216, 279),
+    ("Legal", 216, 356),
+    ("Executive", 184, 267),
+    ("Tabloid", 279, 432),
+];
+
+/// An input tray from the `prtInputTable`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Tray {
+    /// Tray name as reported by the printer, e.g. `Tray 2`.
+    pub name: String,
+    /// Name of the loaded media, e.g. `Plain`.
+    pub media_name: Option<String>,
+    /// Loaded media size, e.g. `A4` or `100x150 mm`.
+    pub media_size: Option<String>,
+    pub level: i64,
+    pub max_capacity: i64,
+    pub level_percent: Option<i64>,
+    pub state: SupplyState,
+    pub status: SubunitStatus,
+}
+
+impl Tray {
+    /// Creates a new [`Tray`], interpreting the levels like a supply's.
+    pub fn new(name: String, level: i64, max_capacity: i64, status: SubunitStatus) -> Self {
+        let state = SupplyState::from_levels(level, max_capacity);
+
+        Self {
+            name,
+            media_name: None,
+            media_size: None,
+            level,
+            max_capacity,
+            level_percent: state.percent(),
+            state,
+            status,
+        }
+    }
+}
+
+/// Names a media size from its declared dimensions, falling back to `WxH mm`.
+///
+/// Negative dimensions mean "unknown" or "other" and give [`None`], as do units other
+/// than inches and micrometres.
+pub fn media_size(unit: i64, feed: i64, cross_feed: i64) -> Option<String> {
+    if feed <= 0 || cross_feed <= 0 {
+        return None;
+    }
+
+    let to_mm = |value: i64| match unit {
+        DIM_UNIT_TEN_THOUSANDTHS_OF_INCHES => Some((value as f64 * 0.00254).round() as i64),
+        DIM_UNIT_MICROMETERS => Some((value as f64 / 1000.0).round() as i64),
+        _ => None,
+    };
+
+    let (a, b) = (to_mm(feed)?, to_mm(cross_feed)?);
+    let (short, long) = (a.min(b), a.max(b));
+
+    let name = MEDIA_SIZES
+        .iter()
+        .find(|(_, s, l)| (short - s).abs() <= 2 && (long - l).abs() <= 2)
+        .map(|(name, _, _)| name.to_string());
+
+    Some(name.unwrap_or_else(|| format!("{short}x{long} mm")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_common_media_sizes() {
+        // A4 in micrometres, fed long edge first
+        assert_eq!(
+            media_size(DIM_UNIT_MICROMETERS, 297_000, 210_000).as_deref(),
+            Some("A4")
+        );
+        // Letter in ten thousandths of an inch, fed short edge first
+        assert_eq!(
+            media_size(DIM_UNIT_TEN_THOUSANDTHS_OF_INCHES, 85_000, 110_000).as_deref(),
+            Some("Letter")
+        );
+        assert_eq!(
+            media_size(DIM_UNIT_MICROMETERS, 100_000, 150_000).as_deref(),
+            Some("100x150 mm")
+        );
+    }
+
+    #[test]
+    fn unknown_dimensions_have_no_size() {
+        assert_eq!(media_size(DIM_UNIT_MICROMETERS, -2, 210_000), None);
+        assert_eq!(media_size(1, 297, 210), None);
+    }
+
+    #[test]
+    fn tray_levels_use_supply_states() {
+        let status = SubunitStatus::from_value(0);
+
+        assert_eq!(
+            Tray::new("Tray 1".to_string(), 125, 250, status).level_percent,
+            Some(50)
+        );
+        assert_eq!(
+            Tray::new("Tray 2".to_string(), -3, 500, status).state,
+            SupplyState::SomeRemaining
+        );
+    }
+}
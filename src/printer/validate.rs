@@ -0,0 +1,438 @@
+use crate::{
+    error::AppError,
+    printer::{load::DataSource, schema::ModelDefinition},
+    utils::parse_oid_to_vec,
+};
+use regex::Regex;
+use serde::{
+    Deserialize, Deserializer,
+    de::{MapAccess, Visitor},
+};
+use serde_json::value::RawValue;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+};
+
+/// A problem found in a data file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    /// The file, labelled like a [`DataEntry`](super::load::DataEntry)'s.
+    pub file: String,
+    /// Line and column, both 1-based, when the problem has a place in the file.
+    pub position: Option<(usize, usize)>,
+    pub message: String,
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.position {
+            Some((line, column)) => write!(f, "{}:{line}:{column}: {}", self.file, self.message),
+            None => write!(f, "{}: {}", self.file, self.message),
+        }
+    }
+}
+
+/// The models of a file in declaration order, keeping duplicate keys that a JSON object
+/// would silently collapse.
+struct Models<'a>(Vec<(String, &'a RawValue)>);
+
+impl<'de> Deserialize<'de> for Models<'de> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ModelsVisitor;
+
+        impl<'de> Visitor<'de> for ModelsVisitor {
+            type Value = Models<'de>;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "an object of models")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut models = Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    models.push(entry);
+                }
+                Ok(Models(models))
+            }
+        }
+
+        deserializer.deserialize_map(ModelsVisitor)
+    }
+}
+
+/// Checks the data files of the given sources.
+///
+/// Models may override each other across sources, so duplicates are only reported
+/// within one source. `extends` may also refer to the bundled models.
+pub fn validate(sources: &[DataSource]) -> Result<Vec<Problem>, AppError> {
+    let checked = sources
+        .iter()
+        .map(DataSource::read_texts)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let reference = if sources.iter().any(|s| matches!(s, DataSource::Bundled)) {
+        Vec::new()
+    } else {
+        DataSource::Bundled.read_texts()?
+    };
+
+    Ok(check_sources(&checked, &reference))
+}
+
+/// Checks the files of each source, given as `(label, text)`. The `reference` files are
+/// only read for the models `extends` can refer to.
+fn check_sources(
+    checked: &[Vec<(String, String)>],
+    reference: &[(String, String)],
+) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let mut parents: BTreeMap<String, Option<String>> = BTreeMap::new();
+    let mut inherited: Vec<(&str, &str, &RawValue, String)> = Vec::new();
+
+    // Reference files first, then the sources from the lowest precedence to the highest,
+    // so that the `extends` of an overriding model wins as it does when loading.
+    let files = reference.iter().map(|file| (None, file)).chain(
+        checked
+            .iter()
+            .enumerate()
+            .rev()
+            .flat_map(|(index, files)| files.iter().map(move |file| (Some(index), file))),
+    );
+
+    let mut declared: Vec<BTreeMap<String, &str>> = vec![BTreeMap::new(); checked.len()];
+
+    for (source, (file, text)) in files {
+        let models = match serde_json::from_str::<Models>(text) {
+            Ok(Models(models)) => models,
+            Err(e) => {
+                if source.is_some() {
+                    problems.push(json_problem(file, text, text, &e));
+                }
+                continue;
+            }
+        };
+
+        for (key, raw) in models {
+            let definition = serde_json::from_str::<ModelDefinition>(raw.get());
+            // Invalid models are reported on their own and treated as having no parent.
+            let parent = definition.as_ref().ok().and_then(|m| m.extends.clone());
+
+            if parent.is_some() || !parents.contains_key(&key) {
+                parents.insert(key.clone(), parent.clone());
+            }
+
+            let Some(source) = source else {
+                continue;
+            };
+
+            let problem = |message: String| Problem {
+                file: file.clone(),
+                position: Some(position(text, offset_of(text, raw.get()))),
+                message,
+            };
+
+            match declared[source].insert(key.clone(), file) {
+                Some(other) if other == file.as_str() => {
+                    problems.push(problem(format!("duplicate model '{key}'")))
+                }
+                Some(other) => {
+                    problems.push(problem(format!("model '{key}' is also defined in {other}")))
+                }
+                None => {}
+            }
+
+            if let Some(regex) = key.strip_prefix('/').and_then(|k| k.strip_suffix('/'))
+                && let Err(e) = Regex::new(&format!("(?i){regex}"))
+            {
+                problems.push(problem(format!(
+                    "'{key}': invalid pattern key: {}",
+                    regex_error(&e)
+                )));
+            }
+
+            problems.extend(check_model(file, text, &key, raw, definition));
+
+            if let Some(parent) = parent {
+                inherited.push((file, text, raw, parent));
+            }
+        }
+    }
+
+    for (file, text, raw, parent) in inherited {
+        let message = if !parents.contains_key(&parent) {
+            Some(format!("extends unknown model '{parent}'"))
+        } else if in_cycle(&parents, &parent) {
+            Some(format!("extends '{parent}' in a cycle"))
+        } else {
+            None
+        };
+
+        if let Some(message) = message {
+            problems.push(Problem {
+                file: file.to_string(),
+                position: Some(position(text, offset_of(text, raw.get()))),
+                message,
+            });
+        }
+    }
+
+    problems.sort_by(|a, b| (&a.file, a.position).cmp(&(&b.file, b.position)));
+    problems
+}
+
+/// Checks one model against the schema, its OIDs and its regular expressions.
+fn check_model(
+    file: &str,
+    text: &str,
+    key: &str,
+    raw: &RawValue,
+    definition: Result<ModelDefinition, serde_json::Error>,
+) -> Vec<Problem> {
+    let start = offset_of(text, raw.get());
+
+    let model = match definition {
+        Ok(model) => model,
+        Err(e) => {
+            let mut problem = json_problem(file, text, raw.get(), &e);
+            problem.message = format!("'{key}': {}", problem.message);
+            return vec![problem];
+        }
+    };
+
+    // Points at the first place the string appears in the model, quoted.
+    let find = |value: &str| {
+        let quoted = serde_json::to_string(value).unwrap_or_default();
+        let offset = raw.get().find(&quoted).map_or(start, |i| start + i);
+        Some(position(text, offset))
+    };
+
+    let mut problems = Vec::new();
+
+    for (path, oid) in model.oids() {
+        let oid_text = match path.as_str() {
+            "match.sys_object_id" => oid.trim_start_matches('.'),
+            _ => oid,
+        };
+
+        if !oid.is_empty() && parse_oid_to_vec(oid_text).is_err() {
+            problems.push(Problem {
+                file: file.to_string(),
+                position: find(oid),
+                message: format!("'{key}': {path}: malformed OID '{oid}'"),
+            });
+        }
+    }
+
+    let descriptions = model
+        .match_rules
+        .map(|rules| rules.description.to_vec())
+        .unwrap_or_default();
+
+    for pattern in descriptions {
+        if let Err(e) = Regex::new(&pattern) {
+            problems.push(Problem {
+                file: file.to_string(),
+                position: find(&pattern),
+                message: format!(
+                    "'{key}': match.description: invalid regular expression: {}",
+                    regex_error(&e)
+                ),
+            });
+        }
+    }
+
+    problems
+}
+
+/// The last line of a regular expression error, without the pattern echoed above it.
+fn regex_error(error: &regex::Error) -> String {
+    let message = error.to_string();
+    let last = message.lines().last().unwrap_or_default();
+    last.strip_prefix("error: ").unwrap_or(last).to_string()
+}
+
+/// Whether following `extends` from `key` leads back to it.
+fn in_cycle(parents: &BTreeMap<String, Option<String>>, key: &str) -> bool {
+    let mut seen = BTreeSet::new();
+    let mut current = Some(key);
+
+    while let Some(key) = current {
+        if !seen.insert(key) {
+            return true;
+        }
+        current = parents.get(key).and_then(Option::as_deref);
+    }
+
+    false
+}
+
+/// Turns a `serde_json` error while parsing `part` of `text` into a [`Problem`] placed
+/// within the whole file.
+fn json_problem(file: &str, text: &str, part: &str, error: &serde_json::Error) -> Problem {
+    let (start_line, start_column) = position(text, offset_of(text, part));
+
+    // `serde_json` appends the position to its messages.
+    let message = error.to_string();
+    let message = message
+        .rsplit_once(" at line ")
+        .map_or(message.as_str(), |(message, _)| message);
+
+    let position = match error.line() {
+        0 => None,
+        1 => Some((start_line, start_column + error.column().saturating_sub(1))),
+        line => Some((start_line + line - 1, error.column())),
+    };
+
+    Problem {
+        file: file.to_string(),
+        position,
+        message: message.to_string(),
+    }
+}
+
+/// The byte offset of `part`, a slice of `text`, within it.
+fn offset_of(text: &str, part: &str) -> usize {
+    (part.as_ptr() as usize).saturating_sub(text.as_ptr() as usize)
+}
+
+/// The 1-based line and column of a byte offset within `text`.
+fn position(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset.min(text.len())];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(files: &[(&str, &str)]) -> Vec<String> {
+        let files = files
+            .iter()
+            .map(|(name, text)| (name.to_string(), text.to_string()))
+            .collect();
+
+        check_sources(&[files], &[])
+            .iter()
+            .map(Problem::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn bundled_data_is_valid() {
+        let problems = validate(&[DataSource::Bundled]).unwrap();
+
+        assert!(problems.is_empty(), "{problems:#?}");
+    }
+
+    #[test]
+    fn reports_syntax_errors_with_their_position() {
+        assert_eq!(
+            check(&[("a.json", "{\n  \"M\": {\n    \"info\": {}\n  },\n}")]),
+            vec!["a.json:5:1: trailing comma"]
+        );
+        assert_eq!(
+            check(&[("a.json", "[]")]),
+            vec!["a.json:1:1: invalid type: sequence, expected an object of models"]
+        );
+    }
+
+    #[test]
+    fn reports_unknown_keys_and_malformed_oids() {
+        let text = r#"{
+  "M": {
+    "toner": {
+      "black": { "levle": "1.3.6.1" }
+    }
+  },
+  "N": {
+    "fuser": { "level": "1.3.6.x", "max_level": "1.3.6.1" },
+    "match": { "description": "C80(" }
+  }
+}"#;
+        let problems = check(&[("a.json", text)]);
+
+        assert_eq!(problems.len(), 3);
+        assert_eq!(
+            problems[0],
+            "a.json:4:24: 'M': unknown field `levle`, expected `level` or `max_level`"
+        );
+        assert_eq!(
+            problems[1],
+            "a.json:8:25: 'N': fuser.level: malformed OID '1.3.6.x'"
+        );
+        assert_eq!(
+            problems[2],
+            "a.json:9:31: 'N': match.description: invalid regular expression: unclosed group"
+        );
+    }
+
+    #[test]
+    fn reports_malformed_oids_in_transforms() {
+        assert_eq!(
+            check(&[(
+                "a.json",
+                r#"{ "M": { "metrics": { "color_impressions": { "sum": ["1.1", "1.x"] } } } }"#
+            )]),
+            vec!["a.json:1:61: 'M': metrics.color_impressions.sum.1: malformed OID '1.x'"]
+        );
+    }
+
+    #[test]
+    fn reports_duplicate_models() {
+        let problems = check(&[
+            ("a.json", "{\n  \"M\": {},\n  \"M\": {}\n}"),
+            ("b.json", "{ \"M\": {} }"),
+        ]);
+
+        assert_eq!(
+            problems,
+            vec![
+                "a.json:3:8: duplicate model 'M'",
+                "b.json:1:8: model 'M' is also defined in a.json"
+            ]
+        );
+    }
+
+    #[test]
+    fn models_may_override_across_sources() {
+        let low = vec![("low.json".to_string(), "{ \"M\": {} }".to_string())];
+        let high = vec![("high.json".to_string(), "{ \"M\": {} }".to_string())];
+
+        assert!(check_sources(&[high, low], &[]).is_empty());
+    }
+
+    #[test]
+    fn reports_unknown_and_cyclic_parents() {
+        let problems = check(&[(
+            "a.json",
+            r#"{ "A": { "extends": "B" }, "B": { "extends": "A" }, "C": { "extends": "D" } }"#,
+        )]);
+
+        assert_eq!(
+            problems,
+            vec![
+                "a.json:1:8: extends 'B' in a cycle",
+                "a.json:1:33: extends 'A' in a cycle",
+                "a.json:1:58: extends unknown model 'D'"
+            ]
+        );
+    }
+
+    #[test]
+    fn parents_may_be_bundled() {
+        let reference = DataSource::Bundled.read_texts().unwrap();
+        let files = vec![(
+            "a.json".to_string(),
+            r#"{ "Xerox AltaLink C8035": { "extends": "Xerox AltaLink C80* Multifunction Printer" } }"#
+                .to_string(),
+        )];
+
+        assert!(check_sources(&[files], &reference).is_empty());
+    }
+}
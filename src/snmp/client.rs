@@ -0,0 +1,167 @@
+use super::{
+    SnmpClientParams, create_snmp_session,
+    request::{PduRequest, send_request},
+    value::{FromSnmpValue, SnmpValues, collect_values},
+    version::SnmpVersion,
+    walk::{WalkRow, collect_batch},
+};
+use crate::error::{AppError, ErrorKind};
+use snmp2::{AsyncSession, Oid, Pdu, Value};
+use tokio::sync::Mutex;
+
+/// Number of OIDs packed into a single GET PDU before the request is split.
+const MAX_OIDS_PER_REQUEST: usize = 24;
+
+/// Number of varbinds requested per GETBULK PDU.
+const BULK_MAX_REPETITIONS: u32 = 16;
+
+/// A reusable SNMP client that owns a single agent session.
+///
+/// The session is created once, so SNMPv3 engine discovery runs a single time and the
+/// engine ID and boots/time learned from it are kept for every following request.
+/// Requests are serialised through the session lock.
+pub struct SnmpClient {
+    params: SnmpClientParams,
+    session: Mutex<AsyncSession>,
+}
+
+impl SnmpClient {
+    /// Establishes the session described by `params`.
+    pub async fn new(params: SnmpClientParams) -> Result<Self, AppError> {
+        let session = create_snmp_session(&params).await?;
+
+        Ok(Self {
+            params,
+            session: Mutex::new(session),
+        })
+    }
+
+    /// The parameters this client was built from.
+    pub fn params(&self) -> &SnmpClientParams {
+        &self.params
+    }
+
+    /// Sends a request PDU over the shared session, with timeout, retry and reconnect handling.
+    async fn send<R>(
+        &self,
+        request: &PduRequest<'_>,
+        handle: impl FnOnce(Pdu<'_>) -> Result<R, AppError>,
+    ) -> Result<R, AppError> {
+        let mut session = self.session.lock().await;
+        send_request(&mut session, &self.params, request, handle).await
+    }
+
+    /// Retrieves a single SNMP value for the specified OID.
+    pub async fn get<T>(&self, oid: &[u64]) -> Result<T, AppError>
+    where
+        T: FromSnmpValue,
+    {
+        let oid_obj = Oid::from(oid).map_err(|_| AppError::new(ErrorKind::OidConversion))?;
+
+        self.send(&PduRequest::Get(&oid_obj), |mut response| {
+            match response.varbinds.next() {
+                Some((_, Value::NoSuchObject | Value::NoSuchInstance | Value::EndOfMibView)) => {
+                    Err(AppError::new(ErrorKind::OidNotFound))
+                }
+                Some((_, value)) => T::from_snmp_value(value),
+                None => Err(AppError::new(ErrorKind::OidNotFound)),
+            }
+        })
+        .await
+    }
+
+    /// Retrieves many SNMP values using as few GET PDUs as possible.
+    ///
+    /// OIDs are packed into requests of up to [`MAX_OIDS_PER_REQUEST`] varbinds. When the
+    /// agent answers `tooBig` (or an SNMPv1 agent rejects the whole PDU with `noSuchName`),
+    /// the chunk is split in half and retried. OIDs the agent does not know are simply
+    /// absent from the result.
+    pub async fn get_many(&self, oids: &[Vec<u64>]) -> Result<SnmpValues, AppError> {
+        let mut unique: Vec<Vec<u64>> = Vec::with_capacity(oids.len());
+        for oid in oids {
+            if !unique.contains(oid) {
+                unique.push(oid.clone());
+            }
+        }
+
+        let mut values = SnmpValues::default();
+        let mut pending: Vec<&[Vec<u64>]> = unique.chunks(MAX_OIDS_PER_REQUEST).rev().collect();
+
+        while let Some(chunk) = pending.pop() {
+            let oid_objs = chunk
+                .iter()
+                .map(|oid| Oid::from(oid).map_err(|_| AppError::new(ErrorKind::OidConversion)))
+                .collect::<Result<Vec<_>, _>>()?;
+            let oid_refs: Vec<&Oid> = oid_objs.iter().collect();
+
+            let result = self
+                .send(&PduRequest::GetMany(&oid_refs), |response| {
+                    collect_values(response.varbinds)
+                })
+                .await;
+
+            match result {
+                Ok(found) => values.extend(found),
+                Err(e)
+                    if chunk.len() > 1
+                        && matches!(
+                            e.kind(),
+                            ErrorKind::ResponseTooBig | ErrorKind::OidNotFound
+                        ) =>
+                {
+                    let (first, second) = chunk.split_at(chunk.len() / 2);
+                    pending.push(second);
+                    pending.push(first);
+                }
+                Err(e) if matches!(e.kind(), ErrorKind::OidNotFound) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(values)
+    }
+
+    /// Walks every value below `root` and converts each one through [`FromSnmpValue`].
+    ///
+    /// Uses GETBULK on SNMPv2c/v3 and GETNEXT on SNMPv1.
+    pub async fn walk<T>(&self, root: &[u64]) -> Result<Vec<WalkRow<T>>, AppError>
+    where
+        T: FromSnmpValue,
+    {
+        let mut rows = Vec::new();
+        let mut current = root.to_vec();
+
+        loop {
+            let oid =
+                Oid::from(&current[..]).map_err(|_| AppError::new(ErrorKind::OidConversion))?;
+
+            let request = match self.params.version {
+                SnmpVersion::V1 => PduRequest::GetNext(&oid),
+                SnmpVersion::V2c | SnmpVersion::V3 => {
+                    PduRequest::GetBulk(&oid, BULK_MAX_REPETITIONS)
+                }
+            };
+
+            let batch = match self
+                .send(&request, |response| {
+                    collect_batch::<T>(root, &current, response.varbinds)
+                })
+                .await
+            {
+                Ok(batch) => batch,
+                // SNMPv1 agents signal the end of the MIB with `noSuchName`.
+                Err(e) if matches!(e.kind(), ErrorKind::OidNotFound) => break,
+                Err(e) => return Err(e),
+            };
+
+            rows.extend(batch.rows);
+
+            match batch.next {
+                Some(next) => current = next,
+                None => break,
+            }
+        }
+
+        Ok(rows)
+    }
+}
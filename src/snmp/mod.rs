@@ -1,20 +1,29 @@
 use crate::{
-    cli::args::Args,
+    cli::{args::Args, output::OutputFormat},
+    driver::printer_mib::PrinterMibDriver,
     error::{AppError, ErrorKind},
-    printer::{Printer, driver::DriverManager},
+    printer::{
+        Printer,
+        driver::DriverManager,
+        identity::{self, Identity},
+        status::{DeviceStatus, PrinterError, PrinterStatus},
+    },
     snmp::security::{AuthProtocol, PrivacyProtocol, SecurityLevel},
 };
+use client::SnmpClient;
 use snmp2::{
     AsyncSession,
     v3::{self},
 };
 use std::{net::Ipv4Addr, path::PathBuf, time::Duration};
-use value::get_snmp_value;
 use version::SnmpVersion;
 
+pub mod client;
+mod request;
 pub mod security;
 pub mod value;
 pub mod version;
+pub mod walk;
 
 /// Encapsulates all necessary parameters to establish an SNMP connection.
 #[derive(Clone, Debug)]
@@ -35,10 +44,15 @@ pub struct SnmpClientParams {
     pub data_dir: Option<PathBuf>,
     pub extra_supplies: bool,
     pub metrics: bool,
+    pub paper: bool,
+    pub info: bool,
 }
 
 impl SnmpClientParams {
     /// Constructs client parameters from parsed CLI arguments and resolved IP.
+    ///
+    /// The Nagios check judges every toner, drum, fuser and reservoir, so it always
+    /// queries the extra supplies.
     pub fn from_args(args: &Args, ip: Ipv4Addr) -> Self {
         Self {
             ip,
@@ -55,8 +69,10 @@ impl SnmpClientParams {
             timeout: Duration::from_secs(args.timeout),
             retries: args.retries,
             data_dir: args.data_dir.clone(),
-            extra_supplies: args.extra_supplies,
+            extra_supplies: args.extra_supplies || matches!(args.output, OutputFormat::Nagios),
             metrics: args.metrics,
+            paper: args.paper,
+            info: args.info,
         }
     }
 }
@@ -146,18 +162,223 @@ async fn build_v3_session(
 }
 
 /// Retrieves the printer name (hrDeviceDescr) via SNMP.
-pub async fn get_printer_name(ctx: &SnmpClientParams) -> Result<String, AppError> {
+pub async fn get_printer_name(client: &SnmpClient) -> Result<String, AppError> {
     const HR_DEVICE_DESCR_OID: &[u64] = &[1, 3, 6, 1, 2, 1, 25, 3, 2, 1, 3, 1];
-    get_snmp_value::<String>(HR_DEVICE_DESCR_OID, ctx).await
+    client.get::<String>(HR_DEVICE_DESCR_OID).await
+}
+
+/// Retrieves the vendor's identification of the device (sysObjectID) via SNMP.
+pub async fn get_object_id(client: &SnmpClient) -> Result<Vec<u64>, AppError> {
+    client.get::<Vec<u64>>(SYS_OBJECT_ID_OID).await
+}
+
+/// hrDeviceStatus for the first device.
+const HR_DEVICE_STATUS_OID: &[u64] = &[1, 3, 6, 1, 2, 1, 25, 3, 2, 1, 5, 1];
+
+/// hrPrinterStatus for the first printer.
+const HR_PRINTER_STATUS_OID: &[u64] = &[1, 3, 6, 1, 2, 1, 25, 3, 5, 1, 1, 1];
+
+/// hrPrinterDetectedErrorState for the first printer.
+const HR_PRINTER_DETECTED_ERROR_STATE_OID: &[u64] = &[1, 3, 6, 1, 2, 1, 25, 3, 5, 1, 2, 1];
+
+/// Retrieves the operational status (hrPrinterStatus, hrDeviceStatus) and the
+/// detected error conditions (hrPrinterDetectedErrorState) in a single request.
+pub async fn get_printer_status(
+    client: &SnmpClient,
+) -> Result<
+    (
+        Option<PrinterStatus>,
+        Option<DeviceStatus>,
+        Vec<PrinterError>,
+    ),
+    AppError,
+> {
+    let values = client
+        .get_many(&[
+            HR_PRINTER_STATUS_OID.to_vec(),
+            HR_DEVICE_STATUS_OID.to_vec(),
+            HR_PRINTER_DETECTED_ERROR_STATE_OID.to_vec(),
+        ])
+        .await?;
+
+    let status = values
+        .get::<i64>(HR_PRINTER_STATUS_OID)
+        .ok()
+        .map(PrinterStatus::from_value);
+    let device_status = values
+        .get::<i64>(HR_DEVICE_STATUS_OID)
+        .ok()
+        .map(DeviceStatus::from_value);
+    let errors = values
+        .get::<Vec<u8>>(HR_PRINTER_DETECTED_ERROR_STATE_OID)
+        .map(|bitmask| PrinterError::decode(&bitmask))
+        .unwrap_or_default();
+
+    Ok((status, device_status, errors))
+}
+
+/// SNMPv2 system group scalars.
+const SYS_DESCR_OID: &[u64] = &[1, 3, 6, 1, 2, 1, 1, 1, 0];
+const SYS_OBJECT_ID_OID: &[u64] = &[1, 3, 6, 1, 2, 1, 1, 2, 0];
+const SYS_UP_TIME_OID: &[u64] = &[1, 3, 6, 1, 2, 1, 1, 3, 0];
+const SYS_CONTACT_OID: &[u64] = &[1, 3, 6, 1, 2, 1, 1, 4, 0];
+const SYS_NAME_OID: &[u64] = &[1, 3, 6, 1, 2, 1, 1, 5, 0];
+const SYS_LOCATION_OID: &[u64] = &[1, 3, 6, 1, 2, 1, 1, 6, 0];
+
+/// hrMemorySize
+const HR_MEMORY_SIZE_OID: &[u64] = &[1, 3, 6, 1, 2, 1, 25, 2, 2, 0];
+
+/// entPhysicalFirmwareRev column of the ENTITY-MIB physical table.
+const ENT_PHYSICAL_FIRMWARE_REV_OID: &[u64] = &[1, 3, 6, 1, 2, 1, 47, 1, 1, 1, 1, 9];
+
+/// hrSWRunName column of the running software table.
+const HR_SW_RUN_NAME_OID: &[u64] = &[1, 3, 6, 1, 2, 1, 25, 4, 2, 1, 2];
+
+/// ifPhysAddress column of the interfaces table.
+const IF_PHYS_ADDRESS_OID: &[u64] = &[1, 3, 6, 1, 2, 1, 2, 2, 1, 6];
+
+/// Retrieves the asset details of the device.
+///
+/// The scalars are fetched in a single request. The firmware and MAC address come from
+/// walking tables, and are left out when the agent does not expose them.
+///
+/// The firmware is the first revision in the physical entity table. Agents without one
+/// fall back to the first running software entry, which on some printers is a boot
+/// loader or process name rather than a version.
+pub async fn get_identity(client: &SnmpClient) -> Result<Identity, AppError> {
+    let values = client
+        .get_many(&[
+            SYS_DESCR_OID.to_vec(),
+            SYS_OBJECT_ID_OID.to_vec(),
+            SYS_UP_TIME_OID.to_vec(),
+            SYS_CONTACT_OID.to_vec(),
+            SYS_NAME_OID.to_vec(),
+            SYS_LOCATION_OID.to_vec(),
+            HR_MEMORY_SIZE_OID.to_vec(),
+        ])
+        .await?;
+
+    let text = |oid: &[u64]| {
+        values
+            .get::<String>(oid)
+            .ok()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+
+    let first_text = async |oid: &[u64]| {
+        client
+            .walk::<String>(oid)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|row| row.value.trim().to_string())
+            .find(|text| !text.is_empty())
+    };
+
+    let firmware = match first_text(ENT_PHYSICAL_FIRMWARE_REV_OID).await {
+        Some(firmware) => Some(firmware),
+        None => first_text(HR_SW_RUN_NAME_OID).await,
+    };
+
+    let mac_address = client
+        .walk::<Vec<u8>>(IF_PHYS_ADDRESS_OID)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .find_map(|row| identity::format_mac(&row.value));
+
+    Ok(Identity {
+        system_name: text(SYS_NAME_OID),
+        description: text(SYS_DESCR_OID),
+        object_id: values
+            .get::<Vec<u64>>(SYS_OBJECT_ID_OID)
+            .ok()
+            .map(|oid| oid.iter().map(u64::to_string).collect::<Vec<_>>().join(".")),
+        uptime_seconds: values
+            .get::<u32>(SYS_UP_TIME_OID)
+            .ok()
+            .map(|ticks| u64::from(ticks) / 100),
+        location: text(SYS_LOCATION_OID),
+        contact: text(SYS_CONTACT_OID),
+        firmware,
+        mac_address,
+        memory_kb: values.get::<i64>(HR_MEMORY_SIZE_OID).ok(),
+    })
 }
 
 /// Orchestrates driver selection and data fetching.
-pub async fn get_printer_values(params: &SnmpClientParams) -> Result<Printer, AppError> {
-    let printer_name = get_printer_name(params).await?;
+///
+/// Compatible drivers are tried in priority order. A driver reporting
+/// [`ErrorKind::UnsupportedPrinter`] hands over to the next one.
+///
+/// The identity, operational status, console message, active alerts, paper trays and
+/// output bins come from the standard MIBs whichever driver is used, unless the driver
+/// already mapped the output bins. Devices without them are reported without
+/// those details rather than failing the query.
+pub async fn get_printer_values(client: &SnmpClient) -> Result<Printer, AppError> {
+    let printer_name = get_printer_name(client).await?;
     let manager = DriverManager::new();
 
-    match manager.get_driver(&printer_name) {
-        Some(driver) => driver.get_supplies(params, &printer_name).await,
-        None => Err(AppError::new(ErrorKind::UnsupportedPrinter(printer_name))),
+    for driver in manager.get_drivers(&printer_name) {
+        match driver.get_supplies(client, &printer_name).await {
+            Err(e) if matches!(e.kind(), ErrorKind::UnsupportedPrinter(_)) => continue,
+            Ok(mut printer) => {
+                if let Ok((status, device_status, errors)) = get_printer_status(client).await {
+                    printer.status = status;
+                    printer.device_status = device_status;
+                    printer.errors = errors;
+                }
+
+                if client.params().info {
+                    printer.identity = get_identity(client).await.ok();
+                }
+
+                printer.console_message = PrinterMibDriver
+                    .fetch_console_message(client)
+                    .await
+                    .unwrap_or_default();
+
+                printer.alerts = PrinterMibDriver
+                    .fetch_alerts(client)
+                    .await
+                    .unwrap_or_default();
+
+                if client.params().paper {
+                    printer.trays = PrinterMibDriver
+                        .fetch_trays(client)
+                        .await
+                        .unwrap_or_default();
+
+                    if printer.outputs.is_empty() {
+                        printer.outputs = PrinterMibDriver
+                            .fetch_outputs(client)
+                            .await
+                            .unwrap_or_default();
+                    }
+                }
+
+                return Ok(printer);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    Err(AppError::new(ErrorKind::UnsupportedPrinter(printer_name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[test]
+    fn nagios_output_queries_extra_supplies() {
+        let params = |argv: &[&str]| {
+            SnmpClientParams::from_args(&Args::parse_from(argv), Ipv4Addr::LOCALHOST)
+        };
+
+        assert!(!params(&["inkcheck", "printer"]).extra_supplies);
+        assert!(params(&["inkcheck", "printer", "-o", "nagios"]).extra_supplies);
     }
 }
@@ -0,0 +1,103 @@
+use super::{SnmpClientParams, create_snmp_session};
+use crate::error::{AppError, ErrorKind};
+use snmp2::{AsyncSession, Oid, Pdu};
+use tokio::time::timeout;
+
+/// `tooBig` error status, returned when the response would not fit in a single message.
+const SNMP_ERROR_TOO_BIG: u32 = 1;
+
+/// SNMPv1 `noSuchName` error status, returned when the requested OID does not exist.
+const SNMP_ERROR_NO_SUCH_NAME: u32 = 2;
+
+/// The request PDUs sent to an agent.
+pub enum PduRequest<'a> {
+    /// A GET for a single OID.
+    Get(&'a Oid<'a>),
+    /// A single GET carrying several OIDs.
+    GetMany(&'a [&'a Oid<'a>]),
+    /// A GETNEXT for the OID following the given one.
+    GetNext(&'a Oid<'a>),
+    /// A GETBULK starting after the given OID, with the maximum number of repetitions.
+    GetBulk(&'a Oid<'a>, u32),
+}
+
+/// Sends a request PDU and passes the successful response to `handle`.
+///
+/// On each network timeout, the session is recreated to recover from dead or stale
+/// connections. SNMPv3 handshake errors (time sync, engine discovery) trigger targeted
+/// recovery without consuming a retry attempt.
+pub async fn send_request<R>(
+    session: &mut AsyncSession,
+    ctx: &SnmpClientParams,
+    request: &PduRequest<'_>,
+    handle: impl FnOnce(Pdu<'_>) -> Result<R, AppError>,
+) -> Result<R, AppError> {
+    for attempt in 1..=ctx.retries {
+        let result = timeout(ctx.timeout, async {
+            match request {
+                PduRequest::Get(oid) => session.get(oid).await,
+                PduRequest::GetMany(oids) => session.getmulti(oids).await,
+                PduRequest::GetNext(oid) => session.getnext(oid).await,
+                PduRequest::GetBulk(oid, max_repetitions) => {
+                    session.getbulk(&[*oid], 0, *max_repetitions).await
+                }
+            }
+        })
+        .await;
+
+        match result {
+            // 1. Network timeout — recreate the session to recover from dead connections.
+            //    The recreation is itself guarded by the same timeout. Any failure is
+            //    intentionally ignored: the next iteration will timeout again and
+            //    exhaust the retry budget naturally.
+            Err(_) => {
+                if attempt < ctx.retries
+                    && let Ok(Ok(new_session)) =
+                        timeout(ctx.timeout, create_snmp_session(ctx)).await
+                {
+                    *session = new_session
+                }
+                continue;
+            }
+
+            // 2. SNMP library errors (protocol level)
+            Ok(Err(error)) => match error {
+                // Auto-recovery: time synchronization updated internally by the library
+                snmp2::Error::AuthUpdated => continue,
+
+                // Auto-recovery: missing engine boots requires re-discovery
+                snmp2::Error::AuthFailure(snmp2::v3::AuthErrorKind::EngineBootsNotProvided) => {
+                    let _ = timeout(ctx.timeout, session.init()).await;
+                    continue;
+                }
+
+                // Fatal protocol errors — no point retrying
+                _ => return Err(AppError::new(ErrorKind::SnmpRequest(error.to_string()))),
+            },
+
+            // 3. Successful response
+            Ok(Ok(response)) => {
+                if response.error_status == SNMP_ERROR_TOO_BIG {
+                    return Err(AppError::new(ErrorKind::ResponseTooBig));
+                }
+
+                if response.error_status == SNMP_ERROR_NO_SUCH_NAME {
+                    return Err(AppError::new(ErrorKind::OidNotFound));
+                }
+
+                if response.error_status != 0 {
+                    return Err(AppError::new(ErrorKind::SnmpRequest(format!(
+                        "SNMP logical error: code {}",
+                        response.error_status
+                    ))));
+                }
+
+                return handle(response);
+            }
+        }
+    }
+
+    Err(AppError::new(ErrorKind::SnmpRequest(
+        "Max retries exceeded or connection timed out".to_string(),
+    )))
+}
@@ -1,73 +1,112 @@
-use super::{SnmpClientParams, create_snmp_session};
 use crate::error::{AppError, ErrorKind};
 use snmp2::{Oid, Value};
-use tokio::time::timeout;
+use std::collections::HashMap;
+
+/// Copies the varbinds of a response into owned values, skipping missing objects.
+pub(super) fn collect_values<'a>(
+    varbinds: impl Iterator<Item = (Oid<'a>, Value<'a>)>,
+) -> Result<Vec<(Vec<u64>, OwnedValue)>, AppError> {
+    varbinds
+        .filter(|(_, value)| {
+            !matches!(
+                value,
+                Value::NoSuchObject | Value::NoSuchInstance | Value::EndOfMibView
+            )
+        })
+        .map(|(oid, value)| Ok((oid_to_vec(&oid)?, OwnedValue::from_value(value)?)))
+        .collect()
+}
 
-/// Retrieves a single SNMP value for the specified OID, handling session initialization and retries.
-///
-/// On each network timeout, the session is recreated to recover from dead or stale
-/// connections. SNMPv3 handshake errors (time sync, engine discovery) trigger targeted
-/// recovery without consuming a retry attempt.
-pub async fn get_snmp_value<T>(oid: &[u64], ctx: &SnmpClientParams) -> Result<T, AppError>
-where
-    T: FromSnmpValue,
-{
-    let mut session = create_snmp_session(ctx).await?;
-    let oid_obj = Oid::from(oid).map_err(|_| AppError::new(ErrorKind::OidConversion))?;
-
-    for attempt in 1..=ctx.retries {
-        let result = timeout(ctx.timeout, session.get(&oid_obj)).await;
-
-        match result {
-            // 1. Network timeout — recreate the session to recover from dead connections.
-            //    The recreation is itself guarded by the same timeout. Any failure is
-            //    intentionally ignored: the next iteration will timeout again and
-            //    exhaust the retry budget naturally.
-            Err(_) => {
-                if attempt < ctx.retries
-                    && let Ok(Ok(new_session)) =
-                        timeout(ctx.timeout, create_snmp_session(ctx)).await
-                {
-                    session = new_session
-                }
-                continue;
-            }
+/// An owned copy of an SNMP [`Value`], detached from the response buffer it was decoded from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OwnedValue {
+    Boolean(bool),
+    Integer(i64),
+    OctetString(Vec<u8>),
+    ObjectIdentifier(Vec<u64>),
+    IpAddress([u8; 4]),
+    Counter32(u32),
+    Unsigned32(u32),
+    Timeticks(u32),
+    Counter64(u64),
+    Null,
+}
 
-            // 2. SNMP library errors (protocol level)
-            Ok(Err(error)) => match error {
-                // Auto-recovery: time synchronization updated internally by the library
-                snmp2::Error::AuthUpdated => continue,
-
-                // Auto-recovery: missing engine boots requires re-discovery
-                snmp2::Error::AuthFailure(snmp2::v3::AuthErrorKind::EngineBootsNotProvided) => {
-                    let _ = timeout(ctx.timeout, session.init()).await;
-                    continue;
-                }
-
-                // Fatal protocol errors — no point retrying
-                _ => return Err(AppError::new(ErrorKind::SnmpRequest(error.to_string()))),
-            },
-
-            // 3. Successful response
-            Ok(Ok(mut response)) => {
-                if response.error_status != 0 {
-                    return Err(AppError::new(ErrorKind::SnmpRequest(format!(
-                        "SNMP logical error: code {}",
-                        response.error_status
-                    ))));
-                }
-
-                return match response.varbinds.next() {
-                    Some((_, value)) => T::from_snmp_value(value),
-                    None => Err(AppError::new(ErrorKind::OidNotFound)),
-                };
-            }
-        }
+impl OwnedValue {
+    /// Copies a borrowed [`Value`] into an [`OwnedValue`].
+    pub fn from_value(value: Value) -> Result<Self, AppError> {
+        Ok(match value {
+            Value::Boolean(v) => Self::Boolean(v),
+            Value::Integer(v) => Self::Integer(v),
+            Value::OctetString(bytes) | Value::Opaque(bytes) => Self::OctetString(bytes.to_vec()),
+            Value::ObjectIdentifier(oid) => Self::ObjectIdentifier(oid_to_vec(&oid)?),
+            Value::IpAddress(addr) => Self::IpAddress(addr),
+            Value::Counter32(v) => Self::Counter32(v),
+            Value::Unsigned32(v) => Self::Unsigned32(v),
+            Value::Timeticks(v) => Self::Timeticks(v),
+            Value::Counter64(v) => Self::Counter64(v),
+            _ => Self::Null,
+        })
     }
 
-    Err(AppError::new(ErrorKind::SnmpRequest(
-        "Max retries exceeded or connection timed out".to_string(),
-    )))
+    /// Borrows this value as an SNMP [`Value`] so it can be converted through [`FromSnmpValue`].
+    pub fn as_value(&self) -> Result<Value<'_>, AppError> {
+        Ok(match self {
+            Self::Boolean(v) => Value::Boolean(*v),
+            Self::Integer(v) => Value::Integer(*v),
+            Self::OctetString(bytes) => Value::OctetString(bytes),
+            Self::ObjectIdentifier(oid) => Value::ObjectIdentifier(
+                Oid::from(oid).map_err(|_| AppError::new(ErrorKind::OidConversion))?,
+            ),
+            Self::IpAddress(addr) => Value::IpAddress(*addr),
+            Self::Counter32(v) => Value::Counter32(*v),
+            Self::Unsigned32(v) => Value::Unsigned32(*v),
+            Self::Timeticks(v) => Value::Timeticks(*v),
+            Self::Counter64(v) => Value::Counter64(*v),
+            Self::Null => Value::Null,
+        })
+    }
+}
+
+/// The results of a batched GET, keyed by OID.
+#[derive(Debug, Default, Clone)]
+pub struct SnmpValues {
+    values: HashMap<Vec<u64>, OwnedValue>,
+}
+
+impl SnmpValues {
+    /// Adds the values returned by a single response.
+    pub(super) fn extend(&mut self, values: impl IntoIterator<Item = (Vec<u64>, OwnedValue)>) {
+        self.values.extend(values);
+    }
+
+    /// Converts the value returned for `oid` through [`FromSnmpValue`].
+    ///
+    /// Returns [`ErrorKind::OidNotFound`] if the agent did not return the OID.
+    pub fn get<T>(&self, oid: &[u64]) -> Result<T, AppError>
+    where
+        T: FromSnmpValue,
+    {
+        self.values
+            .get(oid)
+            .ok_or_else(|| AppError::new(ErrorKind::OidNotFound))
+            .and_then(OwnedValue::as_value)
+            .and_then(T::from_snmp_value)
+    }
+}
+
+/// Converts an SNMP [`Oid`] into its numerical components.
+pub fn oid_to_vec(oid: &Oid) -> Result<Vec<u64>, AppError> {
+    oid.to_string()
+        .split('.')
+        .map(|s| {
+            s.parse::<u64>().map_err(|_| {
+                AppError::new(ErrorKind::Parse(format!(
+                    "Failed to parse OID component '{s}'"
+                )))
+            })
+        })
+        .collect()
 }
 
 /// A trait for converting SNMP [`Value`] types into Rust types.
@@ -121,17 +160,7 @@ impl FromSnmpValue for String {
 impl FromSnmpValue for Vec<u64> {
     fn from_snmp_value(value: Value) -> Result<Self, AppError> {
         if let Value::ObjectIdentifier(oid) = value {
-            let oid_string = oid.to_string();
-            oid_string
-                .split('.')
-                .map(|s| {
-                    s.parse::<u64>().map_err(|_| {
-                        AppError::new(ErrorKind::Parse(format!(
-                            "Failed to parse OID component '{s}'"
-                        )))
-                    })
-                })
-                .collect()
+            oid_to_vec(&oid)
         } else {
             Err(AppError::new(ErrorKind::TypeMismatch(
                 "Expected ObjectIdentifier, but received a different type".to_string(),
@@ -192,6 +221,63 @@ mod tests {
     use super::*;
     use snmp2::{Oid, Value};
 
+    mod batched_values {
+        use super::*;
+
+        const SERIAL: &[u64] = &[1, 3, 6, 1, 2, 1, 43, 5, 1, 1, 17, 1];
+        const LEVEL: &[u64] = &[1, 3, 6, 1, 2, 1, 43, 11, 1, 1, 9, 1, 1];
+        const MISSING: &[u64] = &[1, 3, 6, 1, 2, 1, 43, 11, 1, 1, 9, 1, 2];
+
+        fn varbinds() -> Vec<(Oid<'static>, Value<'static>)> {
+            vec![
+                (Oid::from(SERIAL).unwrap(), Value::OctetString(b"XYZ-123")),
+                (Oid::from(LEVEL).unwrap(), Value::Integer(80)),
+                (Oid::from(MISSING).unwrap(), Value::NoSuchInstance),
+            ]
+        }
+
+        #[test]
+        fn maps_results_back_by_oid() {
+            let values = SnmpValues {
+                values: collect_values(varbinds().into_iter())
+                    .unwrap()
+                    .into_iter()
+                    .collect(),
+            };
+
+            assert_eq!(values.get::<String>(SERIAL).unwrap(), "XYZ-123");
+            assert_eq!(values.get::<i64>(LEVEL).unwrap(), 80);
+        }
+
+        #[test]
+        fn missing_objects_are_not_found() {
+            let values = SnmpValues {
+                values: collect_values(varbinds().into_iter())
+                    .unwrap()
+                    .into_iter()
+                    .collect(),
+            };
+
+            assert!(matches!(
+                values.get::<i64>(MISSING).unwrap_err().kind(),
+                ErrorKind::OidNotFound
+            ));
+        }
+
+        #[test]
+        fn owned_value_round_trip() {
+            let components: &[u64] = &[1, 3, 6, 1, 4, 1, 2435];
+            let owned =
+                OwnedValue::from_value(Value::ObjectIdentifier(Oid::from(components).unwrap()))
+                    .unwrap();
+
+            assert_eq!(
+                Vec::<u64>::from_snmp_value(owned.as_value().unwrap()).unwrap(),
+                components
+            );
+        }
+    }
+
     mod i64_conversion {
         use super::*;
 
@@ -434,12 +520,12 @@ mod tests {
 
         #[test]
         fn from_boolean_true() {
-            assert_eq!(bool::from_snmp_value(Value::Boolean(true)).unwrap(), true);
+            assert!(bool::from_snmp_value(Value::Boolean(true)).unwrap());
         }
 
         #[test]
         fn from_boolean_false() {
-            assert_eq!(bool::from_snmp_value(Value::Boolean(false)).unwrap(), false);
+            assert!(!bool::from_snmp_value(Value::Boolean(false)).unwrap());
         }
 
         #[test]
@@ -0,0 +1,170 @@
+use super::value::{FromSnmpValue, oid_to_vec};
+use crate::error::AppError;
+use snmp2::{Oid, Value};
+
+/// A single value found while walking a subtree.
+///
+/// `index` holds the OID components that follow the walked root, which for a table
+/// column is the row index (e.g. `hrDeviceIndex.prtMarkerSuppliesIndex`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct WalkRow<T> {
+    pub index: Vec<u64>,
+    pub value: T,
+}
+
+/// The values collected from one response, and where the walk should continue from.
+pub(super) struct WalkBatch<T> {
+    pub rows: Vec<WalkRow<T>>,
+    pub next: Option<Vec<u64>>,
+}
+
+/// Converts the varbinds of a single response into typed rows.
+///
+/// The walk ends (`next` is [`None`]) when a varbind leaves the subtree, when the agent
+/// reports `endOfMibView`/`noSuchObject`/`noSuchInstance`, or when an OID does not
+/// increase (a misbehaving agent that would otherwise loop forever).
+pub(super) fn collect_batch<'a, T>(
+    root: &[u64],
+    last: &[u64],
+    varbinds: impl Iterator<Item = (Oid<'a>, Value<'a>)>,
+) -> Result<WalkBatch<T>, AppError>
+where
+    T: FromSnmpValue,
+{
+    let mut rows = Vec::new();
+    let mut previous = last.to_vec();
+
+    for (oid, value) in varbinds {
+        let oid = oid_to_vec(&oid)?;
+
+        let finished = matches!(
+            value,
+            Value::EndOfMibView | Value::NoSuchObject | Value::NoSuchInstance
+        );
+
+        if finished || !oid.starts_with(root) || oid.len() == root.len() || oid <= previous {
+            return Ok(WalkBatch { rows, next: None });
+        }
+
+        rows.push(WalkRow {
+            index: oid[root.len()..].to_vec(),
+            value: T::from_snmp_value(value)?,
+        });
+        previous = oid;
+    }
+
+    // An empty response cannot advance the walk.
+    let next = (!rows.is_empty()).then_some(previous);
+
+    Ok(WalkBatch { rows, next })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROOT: &[u64] = &[1, 3, 6, 1, 2, 1, 43, 11, 1, 1, 9];
+
+    fn oid(components: &[u64]) -> Oid<'static> {
+        Oid::from(components).unwrap()
+    }
+
+    fn column(row: u64) -> Vec<u64> {
+        let mut oid = ROOT.to_vec();
+        oid.extend([1, row]);
+        oid
+    }
+
+    #[test]
+    fn collects_rows_within_subtree() {
+        let varbinds = vec![
+            (oid(&column(1)), Value::Integer(80)),
+            (oid(&column(2)), Value::Integer(-3)),
+        ];
+
+        let batch = collect_batch::<i64>(ROOT, ROOT, varbinds.into_iter()).unwrap();
+
+        assert_eq!(
+            batch.rows,
+            vec![
+                WalkRow {
+                    index: vec![1, 1],
+                    value: 80
+                },
+                WalkRow {
+                    index: vec![1, 2],
+                    value: -3
+                },
+            ]
+        );
+        assert_eq!(batch.next, Some(column(2)));
+    }
+
+    #[test]
+    fn stops_when_leaving_subtree() {
+        let mut outside = ROOT.to_vec();
+        *outside.last_mut().unwrap() += 1;
+        outside.extend([1, 1]);
+
+        let varbinds = vec![
+            (oid(&column(1)), Value::Integer(80)),
+            (oid(&outside), Value::Integer(100)),
+        ];
+
+        let batch = collect_batch::<i64>(ROOT, ROOT, varbinds.into_iter()).unwrap();
+
+        assert_eq!(batch.rows.len(), 1);
+        assert_eq!(batch.next, None);
+    }
+
+    #[test]
+    fn stops_at_end_of_mib_view() {
+        let varbinds = vec![
+            (oid(&column(1)), Value::Integer(80)),
+            (oid(&column(2)), Value::EndOfMibView),
+        ];
+
+        let batch = collect_batch::<i64>(ROOT, ROOT, varbinds.into_iter()).unwrap();
+
+        assert_eq!(batch.rows.len(), 1);
+        assert_eq!(batch.next, None);
+    }
+
+    #[test]
+    fn stops_at_no_such_object() {
+        let varbinds = vec![(oid(&column(1)), Value::NoSuchObject)];
+
+        let batch = collect_batch::<i64>(ROOT, ROOT, varbinds.into_iter()).unwrap();
+
+        assert!(batch.rows.is_empty());
+        assert_eq!(batch.next, None);
+    }
+
+    #[test]
+    fn stops_when_oid_does_not_increase() {
+        let varbinds = vec![
+            (oid(&column(2)), Value::Integer(80)),
+            (oid(&column(1)), Value::Integer(50)),
+        ];
+
+        let batch = collect_batch::<i64>(ROOT, ROOT, varbinds.into_iter()).unwrap();
+
+        assert_eq!(batch.rows.len(), 1);
+        assert_eq!(batch.next, None);
+    }
+
+    #[test]
+    fn empty_response_ends_walk() {
+        let batch = collect_batch::<i64>(ROOT, ROOT, std::iter::empty()).unwrap();
+
+        assert!(batch.rows.is_empty());
+        assert_eq!(batch.next, None);
+    }
+
+    #[test]
+    fn type_mismatch_returns_error() {
+        let varbinds = vec![(oid(&column(1)), Value::OctetString(b"Black Toner"))];
+
+        assert!(collect_batch::<i64>(ROOT, ROOT, varbinds.into_iter()).is_err());
+    }
+}